@@ -0,0 +1,98 @@
+//! Бенчмарк HTTP клиента к upstream: новый клиент на каждый запрос против общего клиента с пулом.
+//!
+//! Поднимает локальный mock OpenAI API (`GET /v1/models`) и выполняет одинаковую серию
+//! запросов двумя способами, как это делал прокси до и после перехода на общий клиент
+//! в `AppState`. Против реального `https://api.openai.com` разница больше, так как к
+//! созданию клиента добавляется TCP + TLS handshake на каждый запрос.
+//!
+//! Запуск: `cargo bench --bench upstream_client`
+
+use axum::{routing::get, Json, Router};
+use oa_bypass::{config::UpstreamConfig, upstream::build_http_client};
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+
+/// Количество запросов в каждой серии.
+const REQUESTS: usize = 500;
+/// Количество прогревочных запросов перед замером.
+const WARMUP: usize = 20;
+
+/// Обработчик mock upstream, возвращающий список моделей.
+async fn list_models() -> Json<Value> {
+    Json(json!({
+        "object": "list",
+        "data": [{"id": "gpt-4o", "object": "model", "created": 1, "owned_by": "openai"}]
+    }))
+}
+
+/// Создает HTTP клиент прокси с настройками upstream по умолчанию.
+fn build_client() -> reqwest::Client {
+    build_http_client(&UpstreamConfig::default()).expect("HTTP client")
+}
+
+/// Выполняет один запрос к mock upstream и читает тело ответа.
+async fn call(client: &reqwest::Client, url: &str) {
+    let response = client
+        .get(url)
+        .bearer_auth("sk-bench")
+        .send()
+        .await
+        .expect("request");
+    assert!(response.status().is_success());
+    response.bytes().await.expect("body");
+}
+
+/// Печатает статистику задержек серии.
+fn report(name: &str, mut samples: Vec<Duration>) {
+    samples.sort();
+    let total: Duration = samples.iter().sum();
+    let percentile = |p: f64| samples[((samples.len() as f64 * p) as usize).min(samples.len() - 1)];
+
+    println!(
+        "{:<24} mean {:>9.3?}  p50 {:>9.3?}  p99 {:>9.3?}  total {:>9.3?}",
+        name,
+        total / samples.len() as u32,
+        percentile(0.50),
+        percentile(0.99),
+        total
+    );
+}
+
+#[tokio::main]
+async fn main() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind mock upstream");
+    let url = format!("http://{}/v1/models", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let app = Router::new().route("/v1/models", get(list_models));
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    // Новый клиент (пул, TLS конфигурация) на каждый запрос
+    for _ in 0..WARMUP {
+        call(&build_client(), &url).await;
+    }
+    let mut per_request = Vec::with_capacity(REQUESTS);
+    for _ in 0..REQUESTS {
+        let started = Instant::now();
+        call(&build_client(), &url).await;
+        per_request.push(started.elapsed());
+    }
+
+    // Общий клиент с keep-alive пулом
+    let shared = build_client();
+    for _ in 0..WARMUP {
+        call(&shared, &url).await;
+    }
+    let mut pooled = Vec::with_capacity(REQUESTS);
+    for _ in 0..REQUESTS {
+        let started = Instant::now();
+        call(&shared, &url).await;
+        pooled.push(started.elapsed());
+    }
+
+    println!("{} запросов к mock upstream:", REQUESTS);
+    report("client per request", per_request);
+    report("shared pooled client", pooled);
+}
@@ -0,0 +1,602 @@
+//! Модуль конфигурации сервера.
+//!
+//! Настройки собираются из трех источников, по возрастанию приоритета:
+//! TOML файл (`--config` / `OA_BYPASS_CONFIG`), переменные окружения `OA_BYPASS_*`
+//! и флаги командной строки. Итоговые значения проверяются при старте сервера.
+
+use crate::error::Locale;
+use async_openai::config::OPENAI_API_BASE;
+use axum::http::HeaderName;
+use clap::{Parser, ValueEnum};
+use serde::Deserialize;
+use std::{collections::HashMap, net::SocketAddr, path::PathBuf, time::Duration};
+
+/// Адрес, на котором сервер слушает по умолчанию.
+const DEFAULT_BIND: &str = "0.0.0.0:8080";
+/// Таймаут установки соединения с OpenAI API по умолчанию (секунды).
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+/// Таймаут ожидания данных от OpenAI API по умолчанию (секунды).
+const DEFAULT_READ_TIMEOUT_SECS: u64 = 600;
+/// Максимум простаивающих соединений с upstream в пуле по умолчанию.
+const DEFAULT_POOL_MAX_IDLE_PER_HOST: usize = 64;
+/// Время жизни простаивающего соединения в пуле по умолчанию (секунды).
+const DEFAULT_POOL_IDLE_TIMEOUT_SECS: u64 = 90;
+/// Лимит размера тела запроса по умолчанию (32 МБ).
+const DEFAULT_BODY_LIMIT_BYTES: usize = 32 * 1024 * 1024;
+/// Лимит тела `POST /v1/files` по умолчанию (201 МБ: файл до 200 МБ плюс поля формы).
+const DEFAULT_FILE_BODY_LIMIT_BYTES: usize = 201 * 1024 * 1024;
+/// Лимит тела `POST /v1/uploads/{upload_id}/parts` по умолчанию (65 МБ: часть до 64 МБ плюс поля формы).
+const DEFAULT_UPLOAD_PART_BODY_LIMIT_BYTES: usize = 65 * 1024 * 1024;
+
+/// Заголовки клиента, которые по умолчанию передаются в OpenAI API.
+const DEFAULT_FORWARD_HEADERS: [&str; 4] = [
+    "OpenAI-Organization",
+    "OpenAI-Project",
+    "OpenAI-Beta",
+    "Idempotency-Key",
+];
+/// Заголовки, которые прокси выставляет сам и которые нельзя добавить в allowlist.
+const RESERVED_HEADERS: [&str; 4] = ["authorization", "host", "content-length", "content-type"];
+
+/// Модель модерации по умолчанию.
+const DEFAULT_MODERATION_MODEL: &str = "omni-moderation-latest";
+
+/// Формат логов.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Человекочитаемый текст
+    Text,
+    /// JSON (по одному объекту на строку)
+    Json,
+}
+
+/// Режим пересылки JSON тел запросов и ответов в типизированных обработчиках.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ForwardingMode {
+    /// Тела разбираются в типы `async-openai` и сериализуются заново
+    Typed,
+    /// Тела пересылаются исходным JSON, включая поля, неизвестные `async-openai`
+    Lossless,
+}
+
+/// Действие политики модерации при срабатывании категории.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationAction {
+    /// Отклонить запрос с ошибкой `proxy_moderation_rejected`
+    Reject,
+    /// Переслать запрос и пометить ответ заголовком `x-proxy-moderation-flagged`
+    Flag,
+}
+
+/// Флаги командной строки. Каждый флаг также читается из переменной окружения.
+#[derive(Debug, Parser)]
+#[command(name = "oa-bypass", version, about = "OpenAI API passthrough proxy")]
+struct Cli {
+    /// Путь к TOML файлу конфигурации
+    #[arg(short, long, env = "OA_BYPASS_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Адрес, на котором слушает сервер (например, 0.0.0.0:8080)
+    #[arg(long, env = "OA_BYPASS_BIND")]
+    bind: Option<String>,
+
+    /// Базовый URL OpenAI-совместимого API (например, https://api.openai.com/v1)
+    #[arg(long, env = "OA_BYPASS_UPSTREAM_BASE_URL")]
+    upstream_base_url: Option<String>,
+
+    /// Таймаут установки соединения с upstream, секунды
+    #[arg(long, env = "OA_BYPASS_CONNECT_TIMEOUT_SECS")]
+    connect_timeout_secs: Option<u64>,
+
+    /// Таймаут ожидания данных от upstream (между чанками ответа), секунды
+    #[arg(long, env = "OA_BYPASS_READ_TIMEOUT_SECS")]
+    read_timeout_secs: Option<u64>,
+
+    /// Максимум простаивающих keep-alive соединений с upstream в пуле
+    #[arg(long, env = "OA_BYPASS_POOL_MAX_IDLE_PER_HOST")]
+    pool_max_idle_per_host: Option<usize>,
+
+    /// Время, через которое простаивающее соединение закрывается, секунды
+    #[arg(long, env = "OA_BYPASS_POOL_IDLE_TIMEOUT_SECS")]
+    pool_idle_timeout_secs: Option<u64>,
+
+    /// Максимальный размер тела запроса, байты
+    #[arg(long, env = "OA_BYPASS_BODY_LIMIT_BYTES")]
+    body_limit_bytes: Option<usize>,
+
+    /// Максимальный размер тела POST /v1/files, байты
+    #[arg(long, env = "OA_BYPASS_FILE_BODY_LIMIT_BYTES")]
+    file_body_limit_bytes: Option<usize>,
+
+    /// Максимальный размер тела POST /v1/uploads/{upload_id}/parts, байты
+    #[arg(long, env = "OA_BYPASS_UPLOAD_PART_BODY_LIMIT_BYTES")]
+    upload_part_body_limit_bytes: Option<usize>,
+
+    /// Формат логов
+    #[arg(long, env = "OA_BYPASS_LOG_FORMAT", value_enum)]
+    log_format: Option<LogFormat>,
+
+    /// Язык сообщений ошибок прокси (en, ru)
+    #[arg(long, env = "OA_BYPASS_LOCALE")]
+    locale: Option<String>,
+
+    /// Режим пересылки JSON тел в типизированных обработчиках
+    #[arg(long, env = "OA_BYPASS_FORWARDING_MODE", value_enum)]
+    forwarding_mode: Option<ForwardingMode>,
+
+    /// Заголовки клиента, передаваемые в upstream (через запятую)
+    #[arg(long, env = "OA_BYPASS_FORWARD_HEADERS", value_delimiter = ',')]
+    forward_headers: Option<Vec<String>>,
+
+    /// Включить предварительную модерацию chat/responses запросов
+    #[arg(long, env = "OA_BYPASS_MODERATION_ENABLED")]
+    moderation_enabled: Option<bool>,
+
+    /// Модель модерации
+    #[arg(long, env = "OA_BYPASS_MODERATION_MODEL")]
+    moderation_model: Option<String>,
+
+    /// Действие при срабатывании модерации
+    #[arg(long, env = "OA_BYPASS_MODERATION_ACTION", value_enum)]
+    moderation_action: Option<ModerationAction>,
+}
+
+/// Содержимое TOML файла конфигурации. Все поля необязательные.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileConfig {
+    server: FileServerConfig,
+    upstream: FileUpstreamConfig,
+    forwarding: FileForwardingConfig,
+    moderation: FileModerationConfig,
+}
+
+/// Секция `[server]` TOML файла.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileServerConfig {
+    bind: Option<String>,
+    body_limit_bytes: Option<usize>,
+    file_body_limit_bytes: Option<usize>,
+    upload_part_body_limit_bytes: Option<usize>,
+    log_format: Option<LogFormat>,
+    locale: Option<String>,
+}
+
+/// Секция `[upstream]` TOML файла.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileUpstreamConfig {
+    base_url: Option<String>,
+    connect_timeout_secs: Option<u64>,
+    read_timeout_secs: Option<u64>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout_secs: Option<u64>,
+}
+
+/// Секция `[forwarding]` TOML файла.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileForwardingConfig {
+    mode: Option<ForwardingMode>,
+    headers: Option<Vec<String>>,
+}
+
+/// Секция `[moderation]` TOML файла.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileModerationConfig {
+    enabled: Option<bool>,
+    model: Option<String>,
+    action: Option<ModerationAction>,
+    thresholds: Option<HashMap<String, f64>>,
+}
+
+/// Итоговая конфигурация сервера.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Настройки HTTP сервера
+    pub server: ServerConfig,
+    /// Настройки подключения к OpenAI API
+    pub upstream: UpstreamConfig,
+    /// Настройки пересылки запросов
+    pub forwarding: ForwardingConfig,
+    /// Политика предварительной модерации
+    pub moderation: ModerationConfig,
+}
+
+/// Настройки HTTP сервера.
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Адрес, на котором слушает сервер
+    pub bind: SocketAddr,
+    /// Максимальный размер тела запроса, байты
+    pub body_limit_bytes: usize,
+    /// Максимальный размер тела `POST /v1/files`, байты
+    pub file_body_limit_bytes: usize,
+    /// Максимальный размер тела `POST /v1/uploads/{upload_id}/parts`, байты
+    pub upload_part_body_limit_bytes: usize,
+    /// Формат логов
+    pub log_format: LogFormat,
+    /// Язык сообщений ошибок прокси
+    pub locale: Locale,
+}
+
+/// Настройки подключения к OpenAI API.
+#[derive(Debug, Clone)]
+pub struct UpstreamConfig {
+    /// Базовый URL API без завершающего `/` (например, `https://api.openai.com/v1`)
+    pub base_url: String,
+    /// Таймаут установки соединения
+    pub connect_timeout: Duration,
+    /// Таймаут ожидания данных между чанками ответа
+    pub read_timeout: Duration,
+    /// Максимум простаивающих keep-alive соединений в пуле
+    pub pool_max_idle_per_host: usize,
+    /// Время, через которое простаивающее соединение закрывается
+    pub pool_idle_timeout: Duration,
+}
+
+impl Default for UpstreamConfig {
+    /// Настройки upstream по умолчанию (официальный OpenAI API).
+    fn default() -> Self {
+        Self {
+            base_url: OPENAI_API_BASE.to_string(),
+            connect_timeout: Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS),
+            read_timeout: Duration::from_secs(DEFAULT_READ_TIMEOUT_SECS),
+            pool_max_idle_per_host: DEFAULT_POOL_MAX_IDLE_PER_HOST,
+            pool_idle_timeout: Duration::from_secs(DEFAULT_POOL_IDLE_TIMEOUT_SECS),
+        }
+    }
+}
+
+/// Настройки пересылки запросов.
+#[derive(Debug, Clone)]
+pub struct ForwardingConfig {
+    /// Режим пересылки JSON тел
+    pub mode: ForwardingMode,
+    /// Заголовки клиента, передаваемые в OpenAI API (allowlist)
+    pub headers: Vec<HeaderName>,
+}
+
+/// Политика предварительной модерации chat/responses запросов.
+#[derive(Debug, Clone)]
+pub struct ModerationConfig {
+    /// Включена ли модерация
+    pub enabled: bool,
+    /// Модель модерации
+    pub model: String,
+    /// Действие при срабатывании
+    pub action: ModerationAction,
+    /// Пороги `category_scores` по категориям; если пусто, используется `flagged` от OpenAI
+    pub thresholds: HashMap<String, f64>,
+}
+
+impl Config {
+    /// Загружает конфигурацию из TOML файла, переменных окружения и флагов командной строки.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Config)` - Проверенная конфигурация
+    /// * `Err(String)` - Описание ошибки чтения файла или недопустимого значения
+    pub fn load() -> Result<Self, String> {
+        let cli = Cli::parse();
+
+        let file = match &cli.config {
+            Some(path) => {
+                let content = std::fs::read_to_string(path)
+                    .map_err(|e| format!("не удалось прочитать {}: {}", path.display(), e))?;
+                toml::from_str(&content)
+                    .map_err(|e| format!("ошибка разбора {}: {}", path.display(), e))?
+            }
+            None => FileConfig::default(),
+        };
+
+        Self::merge(cli, file)
+    }
+
+    /// Объединяет значения флагов/окружения и файла, подставляет значения по умолчанию
+    /// и проверяет результат.
+    fn merge(cli: Cli, file: FileConfig) -> Result<Self, String> {
+        let bind = cli
+            .bind
+            .or(file.server.bind)
+            .unwrap_or_else(|| DEFAULT_BIND.to_string());
+        let bind: SocketAddr = bind
+            .parse()
+            .map_err(|e| format!("недопустимый адрес bind '{}': {}", bind, e))?;
+
+        let body_limit_bytes = cli
+            .body_limit_bytes
+            .or(file.server.body_limit_bytes)
+            .unwrap_or(DEFAULT_BODY_LIMIT_BYTES);
+        if body_limit_bytes == 0 {
+            return Err("body_limit_bytes должен быть больше 0".to_string());
+        }
+
+        let file_body_limit_bytes = cli
+            .file_body_limit_bytes
+            .or(file.server.file_body_limit_bytes)
+            .unwrap_or(DEFAULT_FILE_BODY_LIMIT_BYTES);
+        if file_body_limit_bytes == 0 {
+            return Err("file_body_limit_bytes должен быть больше 0".to_string());
+        }
+
+        let upload_part_body_limit_bytes = cli
+            .upload_part_body_limit_bytes
+            .or(file.server.upload_part_body_limit_bytes)
+            .unwrap_or(DEFAULT_UPLOAD_PART_BODY_LIMIT_BYTES);
+        if upload_part_body_limit_bytes == 0 {
+            return Err("upload_part_body_limit_bytes должен быть больше 0".to_string());
+        }
+
+        let log_format = cli
+            .log_format
+            .or(file.server.log_format)
+            .unwrap_or(LogFormat::Text);
+
+        let locale = match cli.locale.or(file.server.locale) {
+            Some(value) => Locale::parse(&value)
+                .ok_or_else(|| format!("неизвестный locale '{}', допустимо: en, ru", value))?,
+            None => Locale::En,
+        };
+
+        let base_url = cli
+            .upstream_base_url
+            .or(file.upstream.base_url)
+            .unwrap_or_else(|| OPENAI_API_BASE.to_string());
+        let parsed = reqwest::Url::parse(&base_url)
+            .map_err(|e| format!("недопустимый upstream base_url '{}': {}", base_url, e))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(format!(
+                "upstream base_url '{}' должен использовать http или https",
+                base_url
+            ));
+        }
+        let base_url = base_url.trim_end_matches('/').to_string();
+
+        let connect_timeout_secs = cli
+            .connect_timeout_secs
+            .or(file.upstream.connect_timeout_secs)
+            .unwrap_or(DEFAULT_CONNECT_TIMEOUT_SECS);
+        let read_timeout_secs = cli
+            .read_timeout_secs
+            .or(file.upstream.read_timeout_secs)
+            .unwrap_or(DEFAULT_READ_TIMEOUT_SECS);
+        if connect_timeout_secs == 0 || read_timeout_secs == 0 {
+            return Err("таймауты upstream должны быть больше 0".to_string());
+        }
+
+        let pool_max_idle_per_host = cli
+            .pool_max_idle_per_host
+            .or(file.upstream.pool_max_idle_per_host)
+            .unwrap_or(DEFAULT_POOL_MAX_IDLE_PER_HOST);
+        let pool_idle_timeout_secs = cli
+            .pool_idle_timeout_secs
+            .or(file.upstream.pool_idle_timeout_secs)
+            .unwrap_or(DEFAULT_POOL_IDLE_TIMEOUT_SECS);
+
+        let mode = cli
+            .forwarding_mode
+            .or(file.forwarding.mode)
+            .unwrap_or(ForwardingMode::Typed);
+
+        let headers = cli
+            .forward_headers
+            .or(file.forwarding.headers)
+            .unwrap_or_else(|| DEFAULT_FORWARD_HEADERS.map(String::from).to_vec())
+            .iter()
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+            .map(|name| {
+                let header = HeaderName::try_from(name)
+                    .map_err(|e| format!("недопустимый заголовок '{}': {}", name, e))?;
+                if RESERVED_HEADERS.contains(&header.as_str()) {
+                    return Err(format!(
+                        "заголовок '{}' выставляется прокси и не может пересылаться",
+                        name
+                    ));
+                }
+                Ok(header)
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let thresholds = file.moderation.thresholds.unwrap_or_default();
+        if let Some((category, threshold)) = thresholds
+            .iter()
+            .find(|(_, threshold)| !(0.0..=1.0).contains(*threshold))
+        {
+            return Err(format!(
+                "порог модерации для '{}' должен быть от 0 до 1, получено {}",
+                category, threshold
+            ));
+        }
+        let moderation = ModerationConfig {
+            enabled: cli
+                .moderation_enabled
+                .or(file.moderation.enabled)
+                .unwrap_or(false),
+            model: cli
+                .moderation_model
+                .or(file.moderation.model)
+                .unwrap_or_else(|| DEFAULT_MODERATION_MODEL.to_string()),
+            action: cli
+                .moderation_action
+                .or(file.moderation.action)
+                .unwrap_or(ModerationAction::Reject),
+            thresholds,
+        };
+
+        Ok(Self {
+            server: ServerConfig {
+                bind,
+                body_limit_bytes,
+                file_body_limit_bytes,
+                upload_part_body_limit_bytes,
+                log_format,
+                locale,
+            },
+            upstream: UpstreamConfig {
+                base_url,
+                connect_timeout: Duration::from_secs(connect_timeout_secs),
+                read_timeout: Duration::from_secs(read_timeout_secs),
+                pool_max_idle_per_host,
+                pool_idle_timeout: Duration::from_secs(pool_idle_timeout_secs),
+            },
+            forwarding: ForwardingConfig { mode, headers },
+            moderation,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merge(args: &[&str], file: &str) -> Result<Config, String> {
+        let cli = Cli::try_parse_from(std::iter::once("oa-bypass").chain(args.iter().copied()))
+            .expect("valid flags");
+        let file: FileConfig = toml::from_str(file).expect("valid TOML");
+
+        Config::merge(cli, file)
+    }
+
+    #[test]
+    fn defaults_are_applied() {
+        let config = merge(&[], "").unwrap();
+
+        assert_eq!(config.server.bind, DEFAULT_BIND.parse().unwrap());
+        assert_eq!(config.server.body_limit_bytes, DEFAULT_BODY_LIMIT_BYTES);
+        assert_eq!(
+            config.server.file_body_limit_bytes,
+            DEFAULT_FILE_BODY_LIMIT_BYTES
+        );
+        assert_eq!(
+            config.server.upload_part_body_limit_bytes,
+            DEFAULT_UPLOAD_PART_BODY_LIMIT_BYTES
+        );
+        assert_eq!(config.server.locale, Locale::En);
+        assert_eq!(config.upstream.base_url, OPENAI_API_BASE);
+        assert_eq!(config.forwarding.mode, ForwardingMode::Typed);
+        assert_eq!(
+            config.forwarding.headers.len(),
+            DEFAULT_FORWARD_HEADERS.len()
+        );
+        assert!(!config.moderation.enabled);
+        assert_eq!(config.moderation.action, ModerationAction::Reject);
+    }
+
+    #[test]
+    fn flags_override_file() {
+        let file = r#"
+            [server]
+            bind = "127.0.0.1:9000"
+            file_body_limit_bytes = 1048576
+            locale = "ru"
+
+            [upstream]
+            base_url = "https://file.example.com/v1"
+            read_timeout_secs = 30
+        "#;
+
+        let config = merge(
+            &[
+                "--bind",
+                "127.0.0.1:9100",
+                "--upstream-base-url",
+                "http://flag.example.com/v1/",
+            ],
+            file,
+        )
+        .unwrap();
+
+        assert_eq!(config.server.bind, "127.0.0.1:9100".parse().unwrap());
+        assert_eq!(config.upstream.base_url, "http://flag.example.com/v1");
+        // Значения, не переданные флагами, берутся из файла
+        assert_eq!(config.server.locale, Locale::Ru);
+        assert_eq!(config.server.file_body_limit_bytes, 1048576);
+        assert_eq!(config.upstream.read_timeout, Duration::from_secs(30));
+    }
+
+    #[test]
+    fn invalid_bind_is_rejected() {
+        let error = merge(&["--bind", "localhost"], "").unwrap_err();
+
+        assert!(error.contains("bind"), "{}", error);
+    }
+
+    #[test]
+    fn non_http_base_url_is_rejected() {
+        let error = merge(&["--upstream-base-url", "ftp://example.com/v1"], "").unwrap_err();
+
+        assert!(error.contains("http или https"), "{}", error);
+    }
+
+    #[test]
+    fn unknown_locale_is_rejected() {
+        assert!(merge(&["--locale", "de"], "").is_err());
+    }
+
+    #[test]
+    fn zero_limits_are_rejected() {
+        assert!(merge(&["--body-limit-bytes", "0"], "").is_err());
+        assert!(merge(&["--file-body-limit-bytes", "0"], "").is_err());
+        assert!(merge(&["--upload-part-body-limit-bytes", "0"], "").is_err());
+        assert!(merge(&["--read-timeout-secs", "0"], "").is_err());
+    }
+
+    #[test]
+    fn moderation_threshold_outside_unit_range_is_rejected() {
+        let file = r#"
+            [moderation.thresholds]
+            violence = 1.5
+        "#;
+
+        let error = merge(&[], file).unwrap_err();
+
+        assert!(error.contains("violence"), "{}", error);
+    }
+
+    #[test]
+    fn moderation_thresholds_are_loaded() {
+        let file = r#"
+            [moderation]
+            enabled = true
+            action = "flag"
+
+            [moderation.thresholds]
+            violence = 0.5
+            harassment = 0.0
+        "#;
+
+        let config = merge(&[], file).unwrap();
+
+        assert!(config.moderation.enabled);
+        assert_eq!(config.moderation.action, ModerationAction::Flag);
+        assert_eq!(config.moderation.thresholds["violence"], 0.5);
+        assert_eq!(config.moderation.thresholds["harassment"], 0.0);
+    }
+
+    #[test]
+    fn reserved_forward_headers_are_rejected() {
+        for header in ["Authorization", "host", "Content-Length", "content-type"] {
+            let error = merge(
+                &["--forward-headers", &format!("OpenAI-Project,{}", header)],
+                "",
+            )
+            .unwrap_err();
+
+            assert!(error.contains(header), "{}", error);
+        }
+    }
+
+    #[test]
+    fn forward_headers_are_trimmed_and_empty_entries_skipped() {
+        let config = merge(&["--forward-headers", " OpenAI-Project ,,X-Custom"], "").unwrap();
+
+        assert_eq!(config.forwarding.headers, ["openai-project", "x-custom"]);
+    }
+}
@@ -2,26 +2,403 @@
 //!
 //! Содержит типы ошибок и их преобразование в HTTP ответы.
 
-use axum::{http::StatusCode, response::IntoResponse};
+use crate::{request_id, state::AppState};
+use axum::{
+    body::{Body, Bytes},
+    extract::{
+        multipart::MultipartRejection,
+        rejection::{JsonRejection, QueryRejection},
+        ws::rejection::WebSocketUpgradeRejection,
+        State,
+    },
+    http::{
+        header::{CONTENT_LENGTH, CONTENT_TYPE},
+        HeaderMap, HeaderValue, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::{json, Value};
+use std::{fmt, sync::Arc};
+use tracing::{error, warn};
+use uuid::Uuid;
+
+/// Язык сообщений об ошибках прокси.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    /// Английский (по умолчанию)
+    En,
+    /// Русский
+    Ru,
+}
+
+impl Locale {
+    /// Разбирает код языка (`en`, `ru`), неизвестные значения дают `None`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "en" => Some(Locale::En),
+            "ru" => Some(Locale::Ru),
+            _ => None,
+        }
+    }
+}
+
+/// Ошибка прокси, сохраненная в расширениях ответа.
+///
+/// Позволяет [`localize_error`] заново сформировать тело ошибки на языке из
+/// конфигурации (`server.locale`), который недоступен в `IntoResponse`.
+#[derive(Clone)]
+struct ProxyError {
+    error: Arc<AppError>,
+    correlation_id: String,
+}
 
 /// Основной тип ошибки приложения.
 ///
-/// Оборачивает строковое описание ошибки и автоматически преобразуется
-/// в HTTP ответ со статусом 500 Internal Server Error.
-pub struct AppError(pub String);
+/// Ошибки OpenAI API передаются клиенту без изменений (исходный статус и тело
+/// `{"error": {...}}`), чтобы SDK могли корректно обрабатывать их и повторять запросы.
+/// Ошибки самого прокси возвращаются в той же схеме, со стабильным машиночитаемым
+/// `code` (например, `proxy_auth_missing`) и `correlation_id` для поиска в логах.
+#[derive(Debug)]
+pub enum AppError {
+    /// Ошибка, возвращенная OpenAI API.
+    Upstream {
+        /// HTTP статус ответа OpenAI
+        status: StatusCode,
+        /// Заголовки OpenAI, передаваемые клиенту (`Content-Type`, `x-request-id`, `x-ratelimit-*`)
+        headers: Box<HeaderMap>,
+        /// Тело ответа OpenAI без изменений
+        body: Bytes,
+    },
+    /// В запросе нет Authorization заголовка.
+    AuthMissing,
+    /// Authorization заголовок содержит недопустимые символы.
+    AuthMalformed,
+    /// В Authorization заголовке пустой API ключ.
+    AuthEmpty,
+    /// Не удалось разобрать JSON тело запроса.
+    InvalidJson {
+        /// HTTP статус ошибки (400 - синтаксис, 415 - Content-Type, 422 - структура)
+        status: StatusCode,
+        /// Описание ошибки разбора
+        details: String,
+    },
+    /// Не удалось разобрать query string запроса.
+    InvalidQuery(String),
+    /// Не удалось разобрать multipart/form-data тело запроса.
+    MultipartInvalid(String),
+    /// В запросе отсутствует обязательное поле.
+    MissingField(&'static str),
+    /// Тело запроса превышает допустимый размер.
+    BodyTooLarge,
+    /// Не удалось прочитать тело запроса (например, клиент разорвал соединение).
+    BodyRead(String),
+    /// Запрос к WebSocket эндпоинту не является корректным WebSocket upgrade.
+    WebSocketUpgradeInvalid {
+        /// HTTP статус ошибки (400 - заголовки upgrade, 405 - метод, 426 - соединение без upgrade)
+        status: StatusCode,
+        /// Описание ошибки upgrade
+        details: String,
+    },
+    /// Путь запроса не относится к API (`/v1/*`).
+    UnknownEndpoint(String),
+    /// Размер тела потоковой загрузки не совпадает с заявленным.
+    UploadSizeMismatch {
+        /// Размер из `?bytes=` или Content-Length
+        declared: u64,
+        /// Фактически полученное количество байт
+        received: u64,
+    },
+    /// Запрос отклонен политикой модерации (список сработавших категорий).
+    ModerationRejected(Vec<String>),
+    /// Не удалось установить соединение с OpenAI API.
+    UpstreamUnreachable(String),
+    /// Истек таймаут ожидания ответа OpenAI API.
+    UpstreamTimeout(String),
+    /// Ответ OpenAI API не удалось разобрать.
+    UpstreamInvalidResponse(String),
+    /// Внутренняя ошибка прокси.
+    Internal(String),
+}
+
+impl AppError {
+    /// Преобразует ошибку чтения multipart в ошибку прокси.
+    ///
+    /// Превышение лимита размера тела отображается в `BodyTooLarge`, остальные
+    /// ошибки - в `MultipartInvalid`.
+    pub fn from_multipart(e: axum::extract::multipart::MultipartError) -> Self {
+        if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            AppError::BodyTooLarge
+        } else {
+            AppError::MultipartInvalid(e.body_text())
+        }
+    }
+
+    /// Преобразует отказ `Json` экстрактора в ошибку прокси.
+    ///
+    /// Превышение лимита размера тела отображается в `BodyTooLarge`, остальные
+    /// ошибки - в `InvalidJson` с исходным статусом отказа.
+    pub fn from_json_rejection(e: JsonRejection) -> Self {
+        if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            AppError::BodyTooLarge
+        } else {
+            AppError::InvalidJson {
+                status: e.status(),
+                details: e.body_text(),
+            }
+        }
+    }
+
+    /// Преобразует отказ `Query` экстрактора в ошибку прокси.
+    pub fn from_query_rejection(e: QueryRejection) -> Self {
+        AppError::InvalidQuery(e.body_text())
+    }
+
+    /// Преобразует отказ `Multipart` экстрактора (например, неверный `boundary`)
+    /// в ошибку прокси.
+    pub fn from_multipart_rejection(e: MultipartRejection) -> Self {
+        if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            AppError::BodyTooLarge
+        } else {
+            AppError::MultipartInvalid(e.body_text())
+        }
+    }
+
+    /// Преобразует отказ `WebSocketUpgrade` экстрактора (обычный HTTP запрос, неверные
+    /// заголовки upgrade) в ошибку прокси с исходным статусом отказа.
+    pub fn from_websocket_upgrade_rejection(e: WebSocketUpgradeRejection) -> Self {
+        AppError::WebSocketUpgradeInvalid {
+            status: e.status(),
+            details: e.body_text(),
+        }
+    }
+
+    /// Возвращает HTTP статус ошибки прокси.
+    fn status(&self) -> StatusCode {
+        match self {
+            AppError::Upstream { status, .. }
+            | AppError::InvalidJson { status, .. }
+            | AppError::WebSocketUpgradeInvalid { status, .. } => *status,
+            AppError::AuthMissing | AppError::AuthMalformed | AppError::AuthEmpty => {
+                StatusCode::UNAUTHORIZED
+            }
+            AppError::InvalidQuery(_)
+            | AppError::MultipartInvalid(_)
+            | AppError::MissingField(_)
+            | AppError::BodyRead(_)
+            | AppError::UploadSizeMismatch { .. }
+            | AppError::ModerationRejected(_) => StatusCode::BAD_REQUEST,
+            AppError::BodyTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::UnknownEndpoint(_) => StatusCode::NOT_FOUND,
+            AppError::UpstreamUnreachable(_) | AppError::UpstreamInvalidResponse(_) => {
+                StatusCode::BAD_GATEWAY
+            }
+            AppError::UpstreamTimeout(_) => StatusCode::GATEWAY_TIMEOUT,
+            AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Возвращает стабильный машиночитаемый код ошибки прокси.
+    fn code(&self) -> &'static str {
+        match self {
+            AppError::Upstream { .. } => "upstream_error",
+            AppError::AuthMissing => "proxy_auth_missing",
+            AppError::AuthMalformed => "proxy_auth_malformed",
+            AppError::AuthEmpty => "proxy_auth_empty",
+            AppError::InvalidJson { .. } => "proxy_invalid_json",
+            AppError::InvalidQuery(_) => "proxy_invalid_query",
+            AppError::MultipartInvalid(_) => "proxy_multipart_invalid",
+            AppError::MissingField(_) => "proxy_missing_field",
+            AppError::BodyTooLarge => "proxy_body_too_large",
+            AppError::BodyRead(_) => "proxy_body_read_failed",
+            AppError::WebSocketUpgradeInvalid { .. } => "proxy_websocket_upgrade_invalid",
+            AppError::UnknownEndpoint(_) => "proxy_unknown_endpoint",
+            AppError::UploadSizeMismatch { .. } => "proxy_upload_size_mismatch",
+            AppError::ModerationRejected(_) => "proxy_moderation_rejected",
+            AppError::UpstreamUnreachable(_) => "proxy_upstream_unreachable",
+            AppError::UpstreamTimeout(_) => "proxy_upstream_timeout",
+            AppError::UpstreamInvalidResponse(_) => "proxy_upstream_invalid_response",
+            AppError::Internal(_) => "proxy_internal_error",
+        }
+    }
+
+    /// Возвращает сообщение об ошибке на указанном языке.
+    fn message(&self, locale: Locale) -> String {
+        match (self, locale) {
+            (AppError::Upstream { body, .. }, _) => String::from_utf8_lossy(body).into_owned(),
+            (AppError::AuthMissing, Locale::En) => "Authorization header is missing".to_string(),
+            (AppError::AuthMissing, Locale::Ru) => "Authorization заголовок не найден".to_string(),
+            (AppError::AuthMalformed, Locale::En) => {
+                "Authorization header is malformed".to_string()
+            }
+            (AppError::AuthMalformed, Locale::Ru) => {
+                "Неверный формат Authorization заголовка".to_string()
+            }
+            (AppError::AuthEmpty, Locale::En) => "API key is empty".to_string(),
+            (AppError::AuthEmpty, Locale::Ru) => "API ключ пустой".to_string(),
+            (AppError::InvalidJson { details, .. }, Locale::En) => {
+                format!("Failed to parse JSON body: {}", details)
+            }
+            (AppError::InvalidJson { details, .. }, Locale::Ru) => {
+                format!("Ошибка разбора JSON тела запроса: {}", details)
+            }
+            (AppError::InvalidQuery(e), Locale::En) => {
+                format!("Failed to parse query string: {}", e)
+            }
+            (AppError::InvalidQuery(e), Locale::Ru) => {
+                format!("Ошибка разбора query string: {}", e)
+            }
+            (AppError::MultipartInvalid(e), Locale::En) => {
+                format!("Failed to parse multipart body: {}", e)
+            }
+            (AppError::MultipartInvalid(e), Locale::Ru) => {
+                format!("Ошибка разбора multipart: {}", e)
+            }
+            (AppError::MissingField(field), Locale::En) => {
+                format!("Required field `{}` is missing", field)
+            }
+            (AppError::MissingField(field), Locale::Ru) => {
+                format!("Не передано обязательное поле `{}`", field)
+            }
+            (AppError::BodyTooLarge, Locale::En) => "Request body is too large".to_string(),
+            (AppError::BodyTooLarge, Locale::Ru) => "Тело запроса слишком большое".to_string(),
+            (AppError::BodyRead(e), Locale::En) => format!("Failed to read request body: {}", e),
+            (AppError::BodyRead(e), Locale::Ru) => format!("Ошибка чтения тела запроса: {}", e),
+            (AppError::WebSocketUpgradeInvalid { details, .. }, Locale::En) => {
+                format!("Invalid WebSocket upgrade request: {}", details)
+            }
+            (AppError::WebSocketUpgradeInvalid { details, .. }, Locale::Ru) => {
+                format!("Некорректный запрос WebSocket upgrade: {}", details)
+            }
+            (AppError::UnknownEndpoint(path), Locale::En) => format!("Unknown endpoint: {}", path),
+            (AppError::UnknownEndpoint(path), Locale::Ru) => {
+                format!("Неизвестный эндпоинт: {}", path)
+            }
+            (AppError::UploadSizeMismatch { declared, received }, Locale::En) => format!(
+                "Upload body size ({} bytes) does not match the declared size ({} bytes)",
+                received, declared
+            ),
+            (AppError::UploadSizeMismatch { declared, received }, Locale::Ru) => format!(
+                "Размер тела загрузки ({} байт) не совпадает с заявленным ({} байт)",
+                received, declared
+            ),
+            (AppError::ModerationRejected(categories), Locale::En) => format!(
+                "Request was rejected by the moderation policy: {}",
+                categories.join(", ")
+            ),
+            (AppError::ModerationRejected(categories), Locale::Ru) => format!(
+                "Запрос отклонен политикой модерации: {}",
+                categories.join(", ")
+            ),
+            (AppError::UpstreamUnreachable(e), Locale::En) => {
+                format!("OpenAI API is unreachable: {}", e)
+            }
+            (AppError::UpstreamUnreachable(e), Locale::Ru) => {
+                format!("Ошибка соединения с OpenAI: {}", e)
+            }
+            (AppError::UpstreamTimeout(e), Locale::En) => {
+                format!("OpenAI API request timed out: {}", e)
+            }
+            (AppError::UpstreamTimeout(e), Locale::Ru) => {
+                format!("Таймаут запроса к OpenAI: {}", e)
+            }
+            (AppError::UpstreamInvalidResponse(e), Locale::En) => {
+                format!("Failed to parse OpenAI API response: {}", e)
+            }
+            (AppError::UpstreamInvalidResponse(e), Locale::Ru) => {
+                format!("Не удалось разобрать ответ OpenAI: {}", e)
+            }
+            (AppError::Internal(e), Locale::En) => format!("Internal proxy error: {}", e),
+            (AppError::Internal(e), Locale::Ru) => format!("Внутренняя ошибка прокси: {}", e),
+        }
+    }
+
+    /// Формирует тело ошибки прокси
+    /// `{"error": {"message", "type", "param", "code", "correlation_id"}}`.
+    fn body(&self, locale: Locale, correlation_id: &str) -> Value {
+        let error_type = if self.status().is_server_error() {
+            "server_error"
+        } else {
+            "invalid_request_error"
+        };
+        json!({
+            "error": {
+                "message": self.message(locale),
+                "type": error_type,
+                "param": self.param(),
+                "code": self.code(),
+                "correlation_id": correlation_id,
+            }
+        })
+    }
+
+    /// Возвращает имя поля запроса, к которому относится ошибка (`param` в теле ошибки).
+    fn param(&self) -> Option<&'static str> {
+        match self {
+            AppError::MissingField(field) => Some(field),
+            AppError::UploadSizeMismatch { .. } => Some("bytes"),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    /// Форматирует ошибку для логов: статус, код и сообщение на английском.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: {}",
+            self.status(),
+            self.code(),
+            self.message(Locale::En)
+        )
+    }
+}
 
 impl IntoResponse for AppError {
     /// Преобразует ошибку в HTTP ответ.
     ///
     /// # Returns
     ///
-    /// HTTP ответ со статусом 500 и текстовым описанием ошибки.
-    fn into_response(self) -> axum::response::Response {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Ошибка: {}", self.0),
-        )
-            .into_response()
+    /// * Для ошибок OpenAI - исходный статус, тело и заголовки лимитов ответа OpenAI
+    /// * Для ошибок прокси - статус варианта и тело
+    ///   `{"error": {"message", "type", "param", "code", "correlation_id"}}` на английском
+    ///   (на язык из конфигурации его переводит [`localize_error`])
+    fn into_response(self) -> Response {
+        if let AppError::Upstream {
+            status,
+            mut headers,
+            body,
+        } = self
+        {
+            warn!(
+                "⚠️ OpenAI API error: {} {}",
+                status,
+                String::from_utf8_lossy(&body)
+            );
+            headers
+                .entry(CONTENT_TYPE)
+                .or_insert_with(|| HeaderValue::from_static("application/json"));
+            return (status, *headers, body).into_response();
+        }
+
+        let status = self.status();
+        // Совпадает с заголовком x-proxy-request-id ответа
+        let correlation_id = request_id::current().unwrap_or_else(|| Uuid::new_v4().to_string());
+
+        if status.is_server_error() {
+            error!("❌ Proxy error [{}]: {}", correlation_id, self);
+        } else {
+            warn!("⚠️ Proxy error [{}]: {}", correlation_id, self);
+        }
+
+        let body = self.body(Locale::En, &correlation_id);
+        let mut response = (status, Json(body)).into_response();
+        response.extensions_mut().insert(ProxyError {
+            error: Arc::new(self),
+            correlation_id,
+        });
+        response
     }
 }
 
@@ -34,8 +411,31 @@ impl From<String> for AppError {
     ///
     /// # Returns
     ///
-    /// Новый экземпляр `AppError`.
+    /// Новый экземпляр `AppError::Internal` (500 Internal Server Error).
     fn from(s: String) -> Self {
-        AppError(s)
+        AppError::Internal(s)
     }
 }
+
+/// Формирует тело ошибок прокси на языке из конфигурации (`server.locale`).
+///
+/// Используется как `axum::middleware::map_response_with_state`. Ответы без ошибки
+/// прокси (включая ошибки OpenAI) не изменяются.
+pub async fn localize_error(State(state): State<Arc<AppState>>, response: Response) -> Response {
+    let locale = state.config.server.locale;
+    if locale == Locale::En {
+        return response;
+    }
+
+    let Some(proxy_error) = response.extensions().get::<ProxyError>().cloned() else {
+        return response;
+    };
+
+    let body = proxy_error
+        .error
+        .body(locale, &proxy_error.correlation_id)
+        .to_string();
+    let (mut parts, _) = response.into_parts();
+    parts.headers.remove(CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(body))
+}
@@ -0,0 +1,17 @@
+//! OpenAI API Bypass Server
+//!
+//! Библиотека прокси-сервера: конфигурация, роутер, клиент OpenAI API и обработка
+//! ошибок. Используется бинарником `oa-bypass` и бенчмарками из `benches/`.
+
+pub mod config;
+pub mod error;
+mod moderation;
+mod multipart;
+mod payload;
+mod query;
+pub mod request_id;
+pub mod routes;
+pub mod state;
+pub mod upstream;
+mod utils;
+mod websocket;
@@ -4,44 +4,94 @@
 //! Принимает токен от клиента в Authorization заголовке и перенаправляет
 //! запросы к официальному OpenAI API без хранения конфиденциальных данных.
 
-mod error;
-mod routes;
-mod state;
-mod utils;
-
-use state::AppState;
+use axum::{extract::DefaultBodyLimit, middleware};
+use oa_bypass::{
+    config::{Config, LogFormat},
+    error, request_id, routes,
+    state::AppState,
+};
 use std::sync::Arc;
 use tower_http::cors::CorsLayer;
-use tracing::info;
+use tracing::{error, info};
+use tracing_subscriber::EnvFilter;
 
 /// Точка входа приложения.
 ///
-/// Инициализирует логирование, создает состояние приложения, настраивает роутер
-/// с CORS middleware и запускает HTTP сервер на порту 8080.
+/// Загружает конфигурацию, инициализирует логирование, создает состояние приложения,
+/// настраивает роутер с CORS middleware и запускает HTTP сервер на заданном адресе.
 #[tokio::main]
 async fn main() {
-    // Инициализация логирования
-    tracing_subscriber::fmt::init();
+    // Загрузка конфигурации (TOML файл, переменные окружения, флаги командной строки)
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Ошибка конфигурации: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    // Инициализация логирования (уровень задается через RUST_LOG, по умолчанию info)
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    match config.server.log_format {
+        LogFormat::Text => tracing_subscriber::fmt().with_env_filter(filter).init(),
+        LogFormat::Json => tracing_subscriber::fmt()
+            .json()
+            .with_env_filter(filter)
+            .init(),
+    }
+
+    let addr = config.server.bind;
+    let body_limit = config.server.body_limit_bytes;
+    let upstream_base_url = config.upstream.base_url.clone();
+    let moderation_enabled = config.moderation.enabled;
 
-    // Создаем состояние приложения (токен будет приходить от клиента)
-    let state = Arc::new(AppState::new());
+    // Создаем состояние приложения (общий HTTP клиент; токен будет приходить от клиента)
+    let state = match AppState::new(config) {
+        Ok(state) => Arc::new(state),
+        Err(e) => {
+            error!("❌ Ошибка инициализации: {}", e);
+            std::process::exit(1);
+        }
+    };
 
     // Создаем роутер
-    let app = routes::create_router(state).layer(CorsLayer::permissive());
+    let app = routes::create_router(state.clone())
+        .layer(DefaultBodyLimit::max(body_limit))
+        // Язык сообщений об ошибках прокси
+        .layer(middleware::map_response_with_state(
+            state,
+            error::localize_error,
+        ))
+        .layer(middleware::from_fn(request_id::assign_request_id))
+        .layer(CorsLayer::permissive());
 
-    let addr = "0.0.0.0:8080";
     info!("🚀 OpenAI API сервер запущен на http://{}", addr);
     info!("📡 Сервер работает в режиме passthrough");
+    info!("📡 Upstream: {}", upstream_base_url);
+    if moderation_enabled {
+        info!("🛡️ Предварительная модерация chat/responses запросов включена");
+    }
     info!("📡 Токен OpenAI должен передаваться в Authorization заголовке от клиента");
     info!("📡 Доступные эндпоинты:");
-    info!("   Completions: POST /v1/chat/completions, /v1/completions");
+    info!("   Completions: POST/GET/DELETE /v1/chat/completions, POST /v1/completions");
     info!("   Models: GET /v1/models");
+    info!("   Images: POST /v1/images/generations, /v1/images/edits, /v1/images/variations");
+    info!("   Audio: POST /v1/audio/transcriptions, /v1/audio/translations, /v1/audio/speech");
+    info!("   Moderations: POST /v1/moderations");
     info!("   Assistants: POST/GET/DELETE /v1/assistants");
     info!("   Threads: POST/GET/DELETE /v1/threads");
-    info!("   Messages: POST/GET /v1/threads/:id/messages");
-    info!("   Runs: POST/GET /v1/threads/:id/runs");
+    info!("   Messages: POST/GET/DELETE /v1/threads/:id/messages");
+    info!("   Runs: POST/GET /v1/threads/:id/runs, /v1/threads/:id/runs/:id/steps");
     info!("   Files: POST/GET/DELETE /v1/files");
-    info!("   Responses: POST/GET/DELETE /v1/responses");
+    info!("   Uploads: POST /v1/uploads, /v1/uploads/stream");
+    info!("   Batches: POST/GET /v1/batches");
+    info!("   Fine-tuning: POST/GET /v1/fine_tuning/jobs, /v1/fine_tuning/checkpoints/:id/permissions");
+    info!("   Vector Stores: POST/GET/DELETE /v1/vector_stores (files, file_batches, search)");
+    info!("   Responses: POST/GET/DELETE /v1/responses, /v1/responses/input_tokens, /v1/responses/compact");
+    info!("   Conversations: POST/GET/DELETE /v1/conversations, /v1/conversations/:id/items");
+    info!("   Evals: POST/GET/DELETE /v1/evals, /v1/evals/:id/runs, /v1/fine_tuning/alpha/graders");
+    info!("   Realtime: WebSocket /v1/realtime, POST /v1/realtime/client_secrets");
+    info!("   Остальные /v1/*: passthrough в upstream без изменений");
 
     let listener = tokio::net::TcpListener::bind(addr)
         .await
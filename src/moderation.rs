@@ -0,0 +1,308 @@
+//! Модуль предварительной модерации запросов.
+//!
+//! Если политика включена (`[moderation] enabled = true`), пользовательский ввод chat
+//! completions и responses перед пересылкой проверяется через `/v1/moderations` с API
+//! ключом клиента. Сработавшие категории определяются по порогам `category_scores`
+//! из конфигурации (или по `flagged` от OpenAI, если пороги не заданы); запрос
+//! отклоняется или пересылается с пометкой в зависимости от `action`.
+
+use crate::{
+    config::{ModerationAction, ModerationConfig},
+    error::AppError,
+    payload::{JsonBody, JsonReply},
+    upstream::UpstreamClient,
+};
+use axum::{http::HeaderValue, response::Response};
+use serde_json::{json, Value};
+use tracing::warn;
+
+/// Заголовок ответа со списком категорий, сработавших в режиме `flag`.
+pub const MODERATION_FLAGGED_HEADER: &str = "x-proxy-moderation-flagged";
+
+/// Проверяет ввод запроса политикой модерации.
+///
+/// # Arguments
+///
+/// * `config` - Политика модерации
+/// * `client` - Клиент OpenAI API с ключом клиента прокси
+/// * `request` - Тело chat completions или responses запроса
+///
+/// # Returns
+///
+/// * `Ok(Vec<String>)` - Сработавшие категории (пусто, если политика выключена или
+///   ввод чистый); непустой список возможен только в режиме `flag`
+/// * `Err(AppError)` - `ModerationRejected` в режиме `reject` или ошибка запроса к OpenAI
+pub async fn preflight<T>(
+    config: &ModerationConfig,
+    client: &UpstreamClient,
+    request: &JsonBody<T>,
+) -> Result<Vec<String>, AppError> {
+    if !config.enabled {
+        return Ok(Vec::new());
+    }
+
+    let inputs = collect_inputs(&request.to_value());
+    if inputs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Текстовые модели модерации принимают только строки, omni - также изображения
+    let input = if inputs.iter().all(|input| input["type"] == "text") {
+        Value::Array(
+            inputs
+                .into_iter()
+                .map(|input| input["text"].clone())
+                .collect(),
+        )
+    } else {
+        Value::Array(inputs)
+    };
+
+    // Idempotency-Key клиента относится к основному запросу, а не к проверке модерации
+    let response: JsonReply<Value> = client
+        .without_idempotency_key()
+        .post(
+            "/moderations",
+            &json!({ "model": config.model, "input": input }),
+        )
+        .await?;
+    let categories = violated_categories(config, &response.into_inner()?);
+
+    if categories.is_empty() {
+        return Ok(categories);
+    }
+
+    match config.action {
+        ModerationAction::Reject => Err(AppError::ModerationRejected(categories)),
+        ModerationAction::Flag => {
+            warn!("🛡️ Moderation: запрос помечен: {}", categories.join(", "));
+            Ok(categories)
+        }
+    }
+}
+
+/// Добавляет к ответу заголовок `x-proxy-moderation-flagged` со сработавшими категориями.
+pub fn mark(response: &mut Response, categories: &[String]) {
+    if categories.is_empty() {
+        return;
+    }
+
+    if let Ok(value) = HeaderValue::from_str(&categories.join(",")) {
+        response
+            .headers_mut()
+            .insert(MODERATION_FLAGGED_HEADER, value);
+    }
+}
+
+/// Собирает пользовательский ввод из тела chat completions (`messages`) или
+/// responses (`input`) в формате входов `/v1/moderations`.
+fn collect_inputs(body: &Value) -> Vec<Value> {
+    let mut inputs = Vec::new();
+
+    if let Some(messages) = body["messages"].as_array() {
+        for message in messages.iter().filter(|m| m["role"] == "user") {
+            collect_content(&message["content"], &mut inputs);
+        }
+    }
+
+    match &body["input"] {
+        Value::String(text) => inputs.push(json!({ "type": "text", "text": text })),
+        Value::Array(items) => {
+            for item in items.iter().filter(|item| item["role"] == "user") {
+                collect_content(&item["content"], &mut inputs);
+            }
+        }
+        _ => {}
+    }
+
+    inputs
+}
+
+/// Добавляет текст и изображения из `content` сообщения (строки или массива частей).
+fn collect_content(content: &Value, inputs: &mut Vec<Value>) {
+    match content {
+        Value::String(text) => inputs.push(json!({ "type": "text", "text": text })),
+        Value::Array(parts) => {
+            for part in parts {
+                match part["type"].as_str() {
+                    Some("text" | "input_text") => {
+                        inputs.push(json!({ "type": "text", "text": part["text"] }));
+                    }
+                    Some("image_url") => {
+                        inputs.push(json!({ "type": "image_url", "image_url": part["image_url"] }));
+                    }
+                    Some("input_image") if part["image_url"].is_string() => {
+                        inputs.push(json!({
+                            "type": "image_url",
+                            "image_url": { "url": part["image_url"] }
+                        }));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Определяет сработавшие категории по ответу `/v1/moderations`.
+fn violated_categories(config: &ModerationConfig, response: &Value) -> Vec<String> {
+    let mut categories: Vec<String> = Vec::new();
+
+    for result in response["results"].as_array().into_iter().flatten() {
+        if config.thresholds.is_empty() {
+            if result["flagged"] == true {
+                let flagged = result["categories"].as_object().into_iter().flatten();
+                categories.extend(
+                    flagged
+                        .filter(|(_, value)| **value == true)
+                        .map(|(name, _)| name.clone()),
+                );
+            }
+            continue;
+        }
+
+        for (category, threshold) in &config.thresholds {
+            let score = result["category_scores"][category].as_f64().unwrap_or(0.0);
+            if score >= *threshold {
+                categories.push(category.clone());
+            }
+        }
+    }
+
+    categories.sort();
+    categories.dedup();
+    categories
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config(thresholds: &[(&str, f64)]) -> ModerationConfig {
+        ModerationConfig {
+            enabled: true,
+            model: "omni-moderation-latest".to_string(),
+            action: ModerationAction::Reject,
+            thresholds: thresholds
+                .iter()
+                .map(|(category, threshold)| (category.to_string(), *threshold))
+                .collect::<HashMap<_, _>>(),
+        }
+    }
+
+    fn moderation_response() -> Value {
+        json!({
+            "results": [{
+                "flagged": true,
+                "categories": { "violence": true, "harassment": false, "self-harm": true },
+                "category_scores": { "violence": 0.91, "harassment": 0.40, "self-harm": 0.55 }
+            }]
+        })
+    }
+
+    #[test]
+    fn flagged_categories_are_used_without_thresholds() {
+        let categories = violated_categories(&config(&[]), &moderation_response());
+
+        assert_eq!(categories, ["self-harm", "violence"]);
+    }
+
+    #[test]
+    fn unflagged_result_passes_without_thresholds() {
+        let mut response = moderation_response();
+        response["results"][0]["flagged"] = json!(false);
+
+        assert!(violated_categories(&config(&[]), &response).is_empty());
+    }
+
+    #[test]
+    fn thresholds_override_flagged() {
+        let config = config(&[("harassment", 0.4), ("violence", 0.95)]);
+
+        let categories = violated_categories(&config, &moderation_response());
+
+        assert_eq!(categories, ["harassment"]);
+    }
+
+    #[test]
+    fn missing_score_does_not_trigger_threshold() {
+        let config = config(&[("sexual", 0.0001)]);
+
+        assert!(violated_categories(&config, &moderation_response()).is_empty());
+    }
+
+    #[test]
+    fn categories_from_several_results_are_deduplicated() {
+        let response = json!({
+            "results": [
+                { "category_scores": { "violence": 0.9 } },
+                { "category_scores": { "violence": 0.8 } }
+            ]
+        });
+
+        let categories = violated_categories(&config(&[("violence", 0.5)]), &response);
+
+        assert_eq!(categories, ["violence"]);
+    }
+
+    #[test]
+    fn chat_messages_keep_only_user_content() {
+        let body = json!({
+            "messages": [
+                { "role": "system", "content": "be nice" },
+                { "role": "user", "content": "hello" },
+                { "role": "assistant", "content": "hi" },
+                { "role": "user", "content": [
+                    { "type": "text", "text": "look" },
+                    { "type": "image_url", "image_url": { "url": "https://example.com/a.png" } },
+                    { "type": "input_audio", "input_audio": { "data": "...", "format": "wav" } }
+                ]}
+            ]
+        });
+
+        assert_eq!(
+            collect_inputs(&body),
+            [
+                json!({ "type": "text", "text": "hello" }),
+                json!({ "type": "text", "text": "look" }),
+                json!({ "type": "image_url", "image_url": { "url": "https://example.com/a.png" } }),
+            ]
+        );
+    }
+
+    #[test]
+    fn responses_string_input_is_collected() {
+        let body = json!({ "input": "hello" });
+
+        assert_eq!(
+            collect_inputs(&body),
+            [json!({ "type": "text", "text": "hello" })]
+        );
+    }
+
+    #[test]
+    fn responses_input_items_keep_only_user_parts() {
+        let body = json!({
+            "input": [
+                { "role": "developer", "content": "be nice" },
+                { "type": "function_call_output", "call_id": "call_1", "output": "42" },
+                { "role": "user", "content": [
+                    { "type": "input_text", "text": "look" },
+                    { "type": "input_image", "image_url": "https://example.com/a.png" },
+                    { "type": "input_image", "file_id": "file-1" },
+                    { "type": "input_file", "file_id": "file-2" }
+                ]}
+            ]
+        });
+
+        assert_eq!(
+            collect_inputs(&body),
+            [
+                json!({ "type": "text", "text": "look" }),
+                json!({ "type": "image_url", "image_url": { "url": "https://example.com/a.png" } }),
+            ]
+        );
+    }
+}
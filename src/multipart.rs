@@ -0,0 +1,117 @@
+//! Модуль пересылки multipart/form-data запросов.
+//!
+//! Поля формы клиента читаются целиком и пересобираются в форму `reqwest` для OpenAI
+//! без изменений: текстовые поля остаются текстом, файлы сохраняют имя и Content-Type,
+//! повторяющиеся поля (`image[]`, `timestamp_granularities[]`) передаются в исходном порядке.
+
+use crate::error::AppError;
+use axum::extract::{FromRequest, Multipart, Request};
+use reqwest::{
+    multipart::{Form, Part},
+    Body,
+};
+
+/// Форма клиента, подготовленная к отправке в OpenAI API.
+pub struct MultipartForm {
+    form: Form,
+    text_fields: Vec<(String, String)>,
+    file_fields: Vec<String>,
+}
+
+impl<S> FromRequest<S> for MultipartForm
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    /// Читает все поля multipart/form-data запроса клиента.
+    ///
+    /// Ошибки заголовков запроса (нет `Content-Type`, неверный `boundary`) и разбора
+    /// тела возвращаются клиенту в схеме ошибок прокси (`proxy_multipart_invalid`).
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let multipart = Multipart::from_request(req, state)
+            .await
+            .map_err(AppError::from_multipart_rejection)?;
+
+        Self::read(multipart).await
+    }
+}
+
+impl MultipartForm {
+    /// Читает все поля multipart/form-data запроса клиента.
+    ///
+    /// # Arguments
+    ///
+    /// * `multipart` - Экстрактор multipart тела запроса
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MultipartForm)` - Форма для отправки в OpenAI
+    /// * `Err(AppError)` - Тело не удалось разобрать или оно превышает лимит
+    async fn read(mut multipart: Multipart) -> Result<Self, AppError> {
+        let mut form = Form::new();
+        let mut text_fields = Vec::new();
+        let mut file_fields = Vec::new();
+
+        while let Some(field) = multipart
+            .next_field()
+            .await
+            .map_err(AppError::from_multipart)?
+        {
+            let name = field.name().unwrap_or_default().to_string();
+
+            match field.file_name().map(str::to_string) {
+                Some(file_name) => {
+                    let content_type = field.content_type().map(str::to_string);
+                    let bytes = field.bytes().await.map_err(AppError::from_multipart)?;
+
+                    // Bytes передаются в форму без копирования: файлы могут занимать сотни МБ
+                    let length = bytes.len() as u64;
+                    let mut part =
+                        Part::stream_with_length(Body::from(bytes), length).file_name(file_name);
+                    if let Some(content_type) = content_type {
+                        part = part
+                            .mime_str(&content_type)
+                            .map_err(|e| AppError::MultipartInvalid(e.to_string()))?;
+                    }
+                    form = form.part(name.clone(), part);
+                    file_fields.push(name);
+                }
+                None => {
+                    let value = field.text().await.map_err(AppError::from_multipart)?;
+                    form = form.text(name.clone(), value.clone());
+                    text_fields.push((name, value));
+                }
+            }
+        }
+
+        Ok(Self {
+            form,
+            text_fields,
+            file_fields,
+        })
+    }
+
+    /// Возвращает значение текстового поля (первое, если поле повторяется).
+    pub fn text(&self, name: &str) -> Option<&str> {
+        self.text_fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Проверяет, что в форме есть файл с указанным именем поля.
+    pub fn has_file(&self, name: &str) -> bool {
+        self.file_fields.iter().any(|field| field == name)
+    }
+
+    /// Возвращает `true`, если клиент запросил потоковый ответ (`stream=true`).
+    pub fn stream(&self) -> bool {
+        self.text("stream") == Some("true")
+    }
+
+    /// Возвращает форму для отправки в OpenAI.
+    pub fn into_form(self) -> Form {
+        self.form
+    }
+}
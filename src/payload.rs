@@ -0,0 +1,186 @@
+//! Модуль JSON тел запросов и ответов.
+//!
+//! В режиме `typed` тела разбираются в типы `async-openai` и сериализуются заново:
+//! прокси проверяет запрос, но поля, неизвестные закрепленной версии крейта, теряются.
+//! В режиме `lossless` тела пересылаются исходным JSON без изменений. В обоих режимах
+//! прокси извлекает поля, нужные ему самому (`model`, `stream`, `id`, `usage`).
+
+use crate::{config::ForwardingMode, error::AppError, state::AppState};
+use axum::{
+    extract::{FromRequest, Request},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize, Serializer};
+use serde_json::{
+    value::{to_raw_value, RawValue},
+    Value,
+};
+use std::{marker::PhantomData, sync::Arc};
+
+/// Поля тела запроса, которые использует прокси.
+#[derive(Debug, Default, Deserialize)]
+struct RequestMeta {
+    model: Option<Value>,
+    stream: Option<Value>,
+}
+
+/// Поля тела ответа, которые использует прокси.
+#[derive(Debug, Default, Deserialize)]
+struct ReplyMeta {
+    id: Option<Value>,
+    usage: Option<Value>,
+}
+
+/// JSON тело запроса клиента, пересылаемое в OpenAI API.
+///
+/// Экстрактор вместо `Json<T>`: в режиме `typed` тело разбирается в `T`, в режиме
+/// `lossless` проверяется только синтаксис JSON. Ошибки разбора возвращаются клиенту
+/// в схеме ошибок прокси (`proxy_invalid_json`). При сериализации выдается итоговый JSON как есть.
+pub struct JsonBody<T> {
+    raw: Box<RawValue>,
+    meta: RequestMeta,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> JsonBody<T> {
+    /// Возвращает модель из поля `model` (пустая строка, если поле не задано).
+    pub fn model(&self) -> &str {
+        self.meta
+            .model
+            .as_ref()
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+    }
+
+    /// Возвращает `true`, если клиент запросил потоковый ответ (`"stream": true`).
+    pub fn stream(&self) -> bool {
+        self.meta.stream.as_ref().and_then(Value::as_bool) == Some(true)
+    }
+
+    /// Разбирает тело запроса в `serde_json::Value` (для политик, которым нужны
+    /// произвольные поля запроса).
+    pub fn to_value(&self) -> Value {
+        serde_json::from_str(self.raw.get()).unwrap_or_default()
+    }
+}
+
+impl<T> FromRequest<Arc<AppState>> for JsonBody<T>
+where
+    T: DeserializeOwned + Serialize,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        let raw = match state.config.forwarding.mode {
+            ForwardingMode::Typed => {
+                let Json(typed) = Json::<T>::from_request(req, state)
+                    .await
+                    .map_err(AppError::from_json_rejection)?;
+                to_raw_value(&typed).map_err(|e| {
+                    AppError::Internal(format!("Request serialization error: {}", e))
+                })?
+            }
+            ForwardingMode::Lossless => {
+                let Json(raw) = Json::<Box<RawValue>>::from_request(req, state)
+                    .await
+                    .map_err(AppError::from_json_rejection)?;
+                raw
+            }
+        };
+        let meta = serde_json::from_str(raw.get()).unwrap_or_default();
+
+        Ok(Self {
+            raw,
+            meta,
+            _type: PhantomData,
+        })
+    }
+}
+
+impl<T> Serialize for JsonBody<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.raw.serialize(serializer)
+    }
+}
+
+/// JSON ответ OpenAI API, возвращаемый клиенту.
+///
+/// В режиме `typed` ответ разбирается в `T` и сериализуется заново, в режиме `lossless`
+/// возвращается исходный JSON OpenAI без изменений. Вместе с телом клиенту передаются
+/// заголовки OpenAI (`x-request-id`, `x-ratelimit-*`, `openai-processing-ms`).
+pub struct JsonReply<T> {
+    raw: Box<RawValue>,
+    headers: HeaderMap,
+    meta: ReplyMeta,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> JsonReply<T>
+where
+    T: DeserializeOwned + Serialize,
+{
+    /// Разбирает тело ответа OpenAI в соответствии с режимом пересылки.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Тело ответа OpenAI
+    /// * `mode` - Режим пересылки JSON
+    /// * `headers` - Заголовки OpenAI, передаваемые клиенту
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonReply<T>)` - Ответ для клиента
+    /// * `Err(AppError)` - Ответ не является JSON (или не соответствует `T` в режиме `typed`)
+    pub fn parse(bytes: &[u8], mode: ForwardingMode, headers: HeaderMap) -> Result<Self, AppError> {
+        let invalid = |e: serde_json::Error| AppError::UpstreamInvalidResponse(e.to_string());
+
+        let raw = match mode {
+            ForwardingMode::Typed => {
+                let typed: T = serde_json::from_slice(bytes).map_err(invalid)?;
+                to_raw_value(&typed).map_err(invalid)?
+            }
+            ForwardingMode::Lossless => serde_json::from_slice(bytes).map_err(invalid)?,
+        };
+        let meta = serde_json::from_str(raw.get()).unwrap_or_default();
+
+        Ok(Self {
+            raw,
+            headers,
+            meta,
+            _type: PhantomData,
+        })
+    }
+}
+
+impl<T> JsonReply<T> {
+    /// Возвращает идентификатор объекта из поля `id` (пустая строка, если поля нет).
+    pub fn id(&self) -> &str {
+        self.meta
+            .id
+            .as_ref()
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+    }
+
+    /// Разбирает JSON ответа в `T` (для ответов, которые прокси использует сам).
+    pub fn into_inner(self) -> Result<T, AppError>
+    where
+        T: DeserializeOwned,
+    {
+        serde_json::from_str(self.raw.get())
+            .map_err(|e| AppError::UpstreamInvalidResponse(e.to_string()))
+    }
+
+    /// Возвращает `usage.total_tokens`, если OpenAI вернул статистику токенов.
+    pub fn total_tokens(&self) -> Option<u64> {
+        self.meta.usage.as_ref()?.get("total_tokens")?.as_u64()
+    }
+}
+
+impl<T> IntoResponse for JsonReply<T> {
+    fn into_response(self) -> Response {
+        (self.headers, Json(self.raw)).into_response()
+    }
+}
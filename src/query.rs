@@ -0,0 +1,28 @@
+//! Модуль параметров query string.
+//!
+//! Экстрактор [`Query`] заменяет `axum::extract::Query`: ошибки разбора query string
+//! возвращаются клиенту в схеме ошибок прокси (`proxy_invalid_query`), а не текстом axum.
+
+use crate::error::AppError;
+use axum::{extract::FromRequestParts, http::request::Parts};
+use serde::de::DeserializeOwned;
+
+/// Параметры query string запроса, разобранные в `T`.
+pub struct Query<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::extract::Query(value) =
+            axum::extract::Query::<T>::from_request_parts(parts, state)
+                .await
+                .map_err(AppError::from_query_rejection)?;
+
+        Ok(Self(value))
+    }
+}
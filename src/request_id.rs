@@ -0,0 +1,60 @@
+//! Модуль идентификаторов запросов прокси.
+//!
+//! Каждому входящему запросу присваивается `x-proxy-request-id`: он возвращается клиенту
+//! в заголовке ответа, используется как `correlation_id` в ошибках прокси и попадает
+//! во все строки лога запроса вместе с `x-request-id` OpenAI.
+
+use axum::{extract::Request, http::HeaderValue, middleware::Next, response::Response};
+use tracing::{field, info_span, Instrument};
+use uuid::Uuid;
+
+/// Заголовок ответа с идентификатором запроса прокси.
+pub const PROXY_REQUEST_ID_HEADER: &str = "x-proxy-request-id";
+
+tokio::task_local! {
+    /// Идентификатор обрабатываемого запроса.
+    static REQUEST_ID: String;
+}
+
+/// Middleware, присваивающий запросу идентификатор.
+///
+/// Обработка запроса выполняется в span `request` с полями `proxy_request_id`,
+/// `method`, `path` и `upstream_request_id` (заполняется после ответа OpenAI).
+///
+/// # Arguments
+///
+/// * `request` - Входящий HTTP запрос
+/// * `next` - Следующий обработчик в цепочке middleware
+///
+/// # Returns
+///
+/// Ответ обработчика с заголовком `x-proxy-request-id`.
+pub async fn assign_request_id(request: Request, next: Next) -> Response {
+    let id = Uuid::new_v4().to_string();
+    let span = info_span!(
+        "request",
+        proxy_request_id = %id,
+        method = %request.method(),
+        path = %request.uri().path(),
+        upstream_request_id = field::Empty,
+    );
+
+    let mut response = REQUEST_ID
+        .scope(id.clone(), next.run(request))
+        .instrument(span)
+        .await;
+
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response
+            .headers_mut()
+            .insert(PROXY_REQUEST_ID_HEADER, value);
+    }
+
+    response
+}
+
+/// Возвращает идентификатор текущего запроса, если вызов выполняется внутри
+/// [`assign_request_id`].
+pub fn current() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
@@ -2,7 +2,14 @@
 //!
 //! Создание, получение, изменение и удаление ассистентов OpenAI.
 
-use crate::{error::AppError, state::AppState, utils::create_client_from_headers};
+use crate::{
+    error::AppError,
+    payload::{JsonBody, JsonReply},
+    query::Query,
+    routes::ListQuery,
+    state::AppState,
+    utils::create_client_from_headers,
+};
 use async_openai::types::assistants::{
     AssistantObject, CreateAssistantRequest, DeleteAssistantResponse, ListAssistantsResponse,
     ModifyAssistantRequest,
@@ -10,20 +17,19 @@ use async_openai::types::assistants::{
 use axum::{
     extract::{Path, State},
     http::HeaderMap,
-    Json,
 };
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::info;
 
 /// Создает ассистента (Assistants API v2) с параметрами из тела запроса.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения (не используется, токен приходит с клиента)
+/// * `state` - Состояние приложения с настройками upstream
 /// * `headers` - HTTP заголовки запроса, содержащие Authorization токен
 /// * `request` - Тело запроса `CreateAssistantRequest` с настройками ассистента
 ///
 /// # Returns
-/// * `Ok(Json<AssistantObject>)` - Созданный ассистент с его `id`
+/// * `Ok(JsonReply<AssistantObject>)` - Созданный ассистент с его `id`
 /// * `Err(AppError)` - Ошибка при обращении к OpenAI API или валидации токена
 ///
 /// # Пример
@@ -34,151 +40,124 @@ use tracing::{error, info};
 ///   -d '{"name":"My Assistant","model":"gpt-4o-mini"}'
 /// ```
 pub async fn create_assistant(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-    Json(request): Json<CreateAssistantRequest>,
-) -> Result<Json<AssistantObject>, AppError> {
+    request: JsonBody<CreateAssistantRequest>,
+) -> Result<JsonReply<AssistantObject>, AppError> {
     info!("🤖 Create assistant request");
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .assistants()
-        .create(request)
-        .await
-        .map_err(|e| {
-            error!("❌ Create assistant error: {}", e);
-            AppError(format!("Create assistant error: {}", e))
-        })?;
+    let response: JsonReply<AssistantObject> = client.post("/assistants", &request).await?;
 
-    info!("✅ Assistant создан: {}", response.id);
-    Ok(Json(response))
+    info!("✅ Assistant создан: {}", response.id());
+    Ok(response)
 }
 
 /// Возвращает список ассистентов текущего пользователя.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации (`limit`, `order`, `after`, `before`)
 ///
 /// # Returns
-/// * `Ok(Json<ListAssistantsResponse>)` - Страница ассистентов (с пагинацией)
+/// * `Ok(JsonReply<ListAssistantsResponse>)` - Страница ассистентов (с пагинацией)
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn list_assistants(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-) -> Result<Json<ListAssistantsResponse>, AppError> {
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ListAssistantsResponse>, AppError> {
     info!("📋 List assistants request");
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .assistants()
-        .list()
-        .await
-        .map_err(|e| {
-            error!("❌ List assistants error: {}", e);
-            AppError(format!("List assistants error: {}", e))
-        })?;
+    let response: JsonReply<ListAssistantsResponse> =
+        client.get_with_query("/assistants", &query).await?;
 
     info!("✅ Assistants list получен");
-    Ok(Json(response))
+    Ok(response)
 }
 
 /// Возвращает ассистента по `assistant_id`.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `assistant_id` - Идентификатор ассистента
 /// * `headers` - Authorization заголовок клиента
 ///
 /// # Returns
-/// * `Ok(Json<AssistantObject>)` - Найденный ассистент
+/// * `Ok(JsonReply<AssistantObject>)` - Найденный ассистент
 /// * `Err(AppError)` - Ошибка запроса или ассистент не найден
 pub async fn get_assistant(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path(assistant_id): Path<String>,
     headers: HeaderMap,
-) -> Result<Json<AssistantObject>, AppError> {
+) -> Result<JsonReply<AssistantObject>, AppError> {
     info!("🤖 Get assistant request: {}", assistant_id);
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .assistants()
-        .retrieve(&assistant_id)
-        .await
-        .map_err(|e| {
-            error!("❌ Get assistant error: {}", e);
-            AppError(format!("Get assistant error: {}", e))
-        })?;
+    let response: JsonReply<AssistantObject> =
+        client.get(&format!("/assistants/{}", assistant_id)).await?;
 
     info!("✅ Assistant получен");
-    Ok(Json(response))
+    Ok(response)
 }
 
 /// Обновляет ассистента по `assistant_id`.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `assistant_id` - Идентификатор ассистента
 /// * `headers` - Authorization заголовок клиента
 /// * `request` - `ModifyAssistantRequest` с изменяемыми полями
 ///
 /// # Returns
-/// * `Ok(Json<AssistantObject>)` - Обновленный ассистент
+/// * `Ok(JsonReply<AssistantObject>)` - Обновленный ассистент
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn modify_assistant(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path(assistant_id): Path<String>,
     headers: HeaderMap,
-    Json(request): Json<ModifyAssistantRequest>,
-) -> Result<Json<AssistantObject>, AppError> {
+    request: JsonBody<ModifyAssistantRequest>,
+) -> Result<JsonReply<AssistantObject>, AppError> {
     info!("🤖 Modify assistant request: {}", assistant_id);
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .assistants()
-        .update(&assistant_id, request)
-        .await
-        .map_err(|e| {
-            error!("❌ Modify assistant error: {}", e);
-            AppError(format!("Modify assistant error: {}", e))
-        })?;
+    let response: JsonReply<AssistantObject> = client
+        .post(&format!("/assistants/{}", assistant_id), &request)
+        .await?;
 
     info!("✅ Assistant обновлен");
-    Ok(Json(response))
+    Ok(response)
 }
 
 /// Удаляет ассистента по `assistant_id`.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `headers` - Authorization заголовок клиента
 /// * `assistant_id` - Идентификатор ассистента для удаления
 ///
 /// # Returns
-/// * `Ok(Json<DeleteAssistantResponse>)` - Подтверждение удаления
+/// * `Ok(JsonReply<DeleteAssistantResponse>)` - Подтверждение удаления
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn delete_assistant(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path(assistant_id): Path<String>,
-) -> Result<Json<DeleteAssistantResponse>, AppError> {
+) -> Result<JsonReply<DeleteAssistantResponse>, AppError> {
     info!("🤖 Delete assistant request: {}", assistant_id);
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .assistants()
-        .delete(&assistant_id)
-        .await
-        .map_err(|e| {
-            error!("❌ Delete assistant error: {}", e);
-            AppError(format!("Delete assistant error: {}", e))
-        })?;
+    let response: JsonReply<DeleteAssistantResponse> = client
+        .delete(&format!("/assistants/{}", assistant_id))
+        .await?;
 
     info!("✅ Assistant удален");
-    Ok(Json(response))
+    Ok(response)
 }
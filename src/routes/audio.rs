@@ -0,0 +1,136 @@
+//! Обработчики Audio API (Whisper и TTS).
+//!
+//! Транскрипция и перевод аудио принимают multipart/form-data и возвращают ответ
+//! в формате, выбранном клиентом (`json`, `verbose_json`, `text`, `srt`, `vtt` или SSE
+//! при `stream=true`). Синтез речи возвращает аудио потоком с исходным Content-Type.
+
+use crate::{
+    error::AppError, multipart::MultipartForm, payload::JsonBody, state::AppState,
+    utils::create_client_from_headers,
+};
+use async_openai::types::audio::CreateSpeechRequest;
+use axum::{extract::State, http::HeaderMap, response::Response};
+use std::sync::Arc;
+use tracing::info;
+
+/// Транскрибирует аудио через OpenAI Audio API.
+///
+/// Все поля формы (`file`, `model`, `language`, `prompt`, `response_format`,
+/// `temperature`, `timestamp_granularities[]`, `include[]`, `stream` и т.д.) передаются
+/// в OpenAI без изменений. При `stream=true` события транскрипции ретранслируются через SSE.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `form` - Поля multipart/form-data (обязательны `file` и `model`)
+///
+/// # Returns
+/// * `Ok(Response)` - Транскрипция в запрошенном формате или SSE-поток событий
+/// * `Err(AppError)` - Ошибка чтения multipart или запроса к OpenAI
+///
+/// # Пример
+/// ```bash
+/// curl -X POST http://localhost:8080/v1/audio/transcriptions \
+///   -H "Authorization: Bearer sk-..." \
+///   -F "model=gpt-4o-transcribe" \
+///   -F "stream=true" \
+///   -F "file=@./speech.mp3"
+/// ```
+pub async fn create_transcription(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    form: MultipartForm,
+) -> Result<Response, AppError> {
+    if !form.has_file("file") {
+        return Err(AppError::MissingField("file"));
+    }
+    let model = form.text("model").ok_or(AppError::MissingField("model"))?;
+
+    info!(
+        "🎙️ Transcription request: model={}, stream={}",
+        model,
+        form.stream()
+    );
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response = client
+        .post_multipart_relay("/audio/transcriptions", form.into_form())
+        .await?;
+
+    info!("✅ Transcription выполнена");
+    Ok(response)
+}
+
+/// Переводит аудио на английский через OpenAI Audio API.
+///
+/// Поля формы передаются в OpenAI без изменений, ответ возвращается в формате
+/// `response_format` (`json`, `verbose_json`, `text`, `srt`, `vtt`).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `form` - Поля multipart/form-data (обязательны `file` и `model`)
+///
+/// # Returns
+/// * `Ok(Response)` - Перевод в запрошенном формате
+/// * `Err(AppError)` - Ошибка чтения multipart или запроса к OpenAI
+pub async fn create_translation(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    form: MultipartForm,
+) -> Result<Response, AppError> {
+    if !form.has_file("file") {
+        return Err(AppError::MissingField("file"));
+    }
+    let model = form.text("model").ok_or(AppError::MissingField("model"))?;
+
+    info!("🎙️ Translation request: model={}", model);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response = client
+        .post_multipart_relay("/audio/translations", form.into_form())
+        .await?;
+
+    info!("✅ Translation выполнен");
+    Ok(response)
+}
+
+/// Синтезирует речь из текста через OpenAI Audio API.
+///
+/// Аудио передается клиенту по мере генерации с исходным Content-Type
+/// (`audio/mpeg`, `audio/wav`, `audio/opus`...). При `"stream_format": "sse"`
+/// ответ приходит как SSE-поток событий.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `CreateSpeechRequest` с текстом, моделью и голосом
+///
+/// # Returns
+/// * `Ok(Response)` - Аудио поток или SSE-поток событий
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+///
+/// # Пример
+/// ```bash
+/// curl -X POST http://localhost:8080/v1/audio/speech \
+///   -H "Content-Type: application/json" \
+///   -H "Authorization: Bearer sk-..." \
+///   -d '{"model": "gpt-4o-mini-tts", "input": "Hello!", "voice": "alloy"}' \
+///   --output speech.mp3
+/// ```
+pub async fn create_speech(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    request: JsonBody<CreateSpeechRequest>,
+) -> Result<Response, AppError> {
+    info!("🔊 Speech request: model={}", request.model());
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response = client.post_relay("/audio/speech", &request).await?;
+
+    info!("✅ Speech stream открыт");
+    Ok(response)
+}
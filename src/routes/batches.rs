@@ -0,0 +1,132 @@
+//! Обработчики Batch API.
+//!
+//! Асинхронная обработка пакетов запросов из JSONL файла (purpose `batch`) со скидкой 50%.
+//! Входной файл загружается через `POST /v1/files`, результаты скачиваются через
+//! `GET /v1/files/{output_file_id}/content`.
+
+use crate::{
+    error::AppError,
+    payload::{JsonBody, JsonReply},
+    query::Query,
+    routes::ListQuery,
+    state::AppState,
+    utils::create_client_from_headers,
+};
+use async_openai::types::batches::{Batch, BatchRequest, ListBatchesResponse};
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+};
+use std::sync::Arc;
+use tracing::info;
+
+/// Создает batch из ранее загруженного JSONL файла.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `BatchRequest` с `input_file_id`, `endpoint` и `completion_window`
+///
+/// # Returns
+/// * `Ok(JsonReply<Batch>)` - Созданный batch с его `id`
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+///
+/// # Пример
+/// ```bash
+/// curl -X POST http://localhost:8080/v1/batches \
+///   -H "Content-Type: application/json" \
+///   -H "Authorization: Bearer sk-..." \
+///   -d '{"input_file_id":"file-abc123","endpoint":"/v1/chat/completions","completion_window":"24h"}'
+/// ```
+pub async fn create_batch(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    request: JsonBody<BatchRequest>,
+) -> Result<JsonReply<Batch>, AppError> {
+    info!("📦 Create batch request");
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<Batch> = client.post("/batches", &request).await?;
+
+    info!("✅ Batch создан: {}", response.id());
+    Ok(response)
+}
+
+/// Возвращает список batches.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации (`limit`, `after`)
+///
+/// # Returns
+/// * `Ok(JsonReply<ListBatchesResponse>)` - Страница batches (с пагинацией)
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn list_batches(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ListBatchesResponse>, AppError> {
+    info!("📦 List batches request");
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<ListBatchesResponse> =
+        client.get_with_query("/batches", &query).await?;
+
+    info!("✅ Batches list получен");
+    Ok(response)
+}
+
+/// Возвращает batch по `batch_id` (используется для опроса статуса).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `batch_id` - Идентификатор batch
+/// * `headers` - Authorization заголовок клиента
+///
+/// # Returns
+/// * `Ok(JsonReply<Batch>)` - Batch со статусом, счетчиками и `output_file_id`
+/// * `Err(AppError)` - Ошибка запроса или batch не найден
+pub async fn get_batch(
+    State(state): State<Arc<AppState>>,
+    Path(batch_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<JsonReply<Batch>, AppError> {
+    info!("📦 Get batch request: {}", batch_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<Batch> = client.get(&format!("/batches/{}", batch_id)).await?;
+
+    info!("✅ Batch получен");
+    Ok(response)
+}
+
+/// Отменяет batch, находящийся в обработке.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `batch_id` - Идентификатор batch
+/// * `headers` - Authorization заголовок клиента
+///
+/// # Returns
+/// * `Ok(JsonReply<Batch>)` - Batch в статусе `cancelling`
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn cancel_batch(
+    State(state): State<Arc<AppState>>,
+    Path(batch_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<JsonReply<Batch>, AppError> {
+    info!("📦 Cancel batch request: {}", batch_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<Batch> = client
+        .post_empty(&format!("/batches/{}/cancel", batch_id))
+        .await?;
+
+    info!("✅ Batch отменен");
+    Ok(response)
+}
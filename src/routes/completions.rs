@@ -3,28 +3,52 @@
 //! Поддерживает два типа completions:
 //! - Chat Completions (GPT-4, GPT-3.5 Turbo и другие чат-модели)
 //! - Legacy Text Completions (старые модели)
+//!
+//! Chat completions, созданные с `"store": true`, можно просматривать, изменять и удалять.
 
-use crate::{error::AppError, state::AppState, utils::create_client_from_headers};
-use async_openai::types::chat::{CreateChatCompletionRequest, CreateChatCompletionResponse};
+use crate::{
+    error::AppError,
+    moderation,
+    payload::{JsonBody, JsonReply},
+    query::Query,
+    routes::ListQuery,
+    state::AppState,
+    utils::create_client_from_headers,
+};
+use async_openai::types::chat::{
+    ChatCompletionDeleted, ChatCompletionList, ChatCompletionMessageList,
+    CreateChatCompletionRequest, CreateChatCompletionResponse, UpdateChatCompletionRequest,
+};
 use async_openai::types::completions::{CreateCompletionRequest, CreateCompletionResponse};
-use axum::{extract::State, http::HeaderMap, Json};
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+};
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::info;
 
 /// Обработчик для создания chat completion.
 ///
 /// Проксирует запрос к OpenAI API для генерации ответа от чат-модели (GPT-4, GPT-3.5-turbo и т.д.).
 /// Использует токен из Authorization заголовка клиента.
 ///
+/// Если в запросе указано `"stream": true`, ответ ретранслируется клиенту как
+/// `text/event-stream` по мере генерации, включая usage-чанк (`stream_options.include_usage`)
+/// и финальное событие `[DONE]`.
+///
+/// Если включена политика модерации, сообщения пользователя предварительно проверяются
+/// через `/v1/moderations` (см. [`moderation::preflight`]).
+///
 /// # Arguments
 ///
-/// * `_state` - Состояние приложения (не используется, так как токен передается от клиента)
+/// * `state` - Состояние приложения с настройками upstream
 /// * `headers` - HTTP заголовки запроса, содержащие Authorization токен
 /// * `request` - Параметры запроса для создания chat completion
 ///
 /// # Returns
 ///
-/// * `Ok(Json<CreateChatCompletionResponse>)` - Успешный ответ от OpenAI API
+/// * `Ok(Response)` - JSON `CreateChatCompletionResponse` или SSE-поток чанков от OpenAI API
 /// * `Err(AppError)` - Ошибка при выполнении запроса
 ///
 /// # Examples
@@ -37,28 +61,206 @@ use tracing::{error, info};
 ///     "model": "gpt-4",
 ///     "messages": [{"role": "user", "content": "Hello!"}]
 ///   }'
+///
+/// # Потоковый режим
+/// curl -N -X POST http://localhost:8080/v1/chat/completions \
+///   -H "Content-Type: application/json" \
+///   -H "Authorization: Bearer sk-..." \
+///   -d '{
+///     "model": "gpt-4",
+///     "stream": true,
+///     "stream_options": {"include_usage": true},
+///     "messages": [{"role": "user", "content": "Hello!"}]
+///   }'
 /// ```
 pub async fn chat_completions(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-    Json(request): Json<CreateChatCompletionRequest>,
-) -> Result<Json<CreateChatCompletionResponse>, AppError> {
-    info!("💬 Chat completion request: model={}", request.model);
+    request: JsonBody<CreateChatCompletionRequest>,
+) -> Result<Response, AppError> {
+    info!("💬 Chat completion request: model={}", request.model());
 
     // Создаем клиента из Authorization заголовка
-    let client = create_client_from_headers(&headers, false)?;
-
-    let response = client
-        .chat()
-        .create(request)
-        .await
-        .map_err(|e| {
-            error!("❌ Chat completion error: {}", e);
-            AppError(format!("Chat completion error: {}", e))
-        })?;
-
-    info!("✅ Chat completion успешно выполнен");
-    Ok(Json(response))
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    // Предварительная модерация ввода (если включена в конфигурации)
+    let flagged = moderation::preflight(&state.config.moderation, &client, &request).await?;
+
+    if request.stream() {
+        let mut response = client.post_stream("/chat/completions", &request).await?;
+        moderation::mark(&mut response, &flagged);
+
+        info!("✅ Chat completion stream открыт");
+        return Ok(response);
+    }
+
+    let response: JsonReply<CreateChatCompletionResponse> =
+        client.post("/chat/completions", &request).await?;
+
+    info!(
+        "✅ Chat completion успешно выполнен: total_tokens={}",
+        response.total_tokens().unwrap_or_default()
+    );
+    let mut response = response.into_response();
+    moderation::mark(&mut response, &flagged);
+    Ok(response)
+}
+
+/// Возвращает список сохраненных (`"store": true`) chat completions.
+///
+/// Параметры query string (`model`, `limit`, `order`, `after` и фильтры `metadata[key]=value`)
+/// передаются в OpenAI в исходном виде.
+///
+/// # Arguments
+///
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - HTTP заголовки запроса, содержащие Authorization токен
+/// * `query` - Параметры пагинации и фильтры
+///
+/// # Returns
+///
+/// * `Ok(JsonReply<ChatCompletionList>)` - Страница сохраненных chat completions
+/// * `Err(AppError)` - Ошибка при выполнении запроса
+pub async fn list_chat_completions(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ChatCompletionList>, AppError> {
+    info!("💬 List chat completions request");
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<ChatCompletionList> =
+        client.get_with_query("/chat/completions", &query).await?;
+
+    info!("✅ Chat completions list получен");
+    Ok(response)
+}
+
+/// Возвращает сохраненный chat completion по `completion_id`.
+///
+/// # Arguments
+///
+/// * `state` - Состояние приложения с настройками upstream
+/// * `completion_id` - Идентификатор chat completion
+/// * `headers` - HTTP заголовки запроса, содержащие Authorization токен
+///
+/// # Returns
+///
+/// * `Ok(JsonReply<CreateChatCompletionResponse>)` - Сохраненный chat completion
+/// * `Err(AppError)` - Ошибка при выполнении запроса или объект не найден
+pub async fn get_chat_completion(
+    State(state): State<Arc<AppState>>,
+    Path(completion_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<JsonReply<CreateChatCompletionResponse>, AppError> {
+    info!("💬 Get chat completion request: {}", completion_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<CreateChatCompletionResponse> = client
+        .get(&format!("/chat/completions/{}", completion_id))
+        .await?;
+
+    info!("✅ Chat completion получен");
+    Ok(response)
+}
+
+/// Обновляет metadata сохраненного chat completion.
+///
+/// # Arguments
+///
+/// * `state` - Состояние приложения с настройками upstream
+/// * `completion_id` - Идентификатор chat completion
+/// * `headers` - HTTP заголовки запроса, содержащие Authorization токен
+/// * `request` - `UpdateChatCompletionRequest` с новыми `metadata`
+///
+/// # Returns
+///
+/// * `Ok(JsonReply<CreateChatCompletionResponse>)` - Обновленный chat completion
+/// * `Err(AppError)` - Ошибка при выполнении запроса
+pub async fn update_chat_completion(
+    State(state): State<Arc<AppState>>,
+    Path(completion_id): Path<String>,
+    headers: HeaderMap,
+    request: JsonBody<UpdateChatCompletionRequest>,
+) -> Result<JsonReply<CreateChatCompletionResponse>, AppError> {
+    info!("💬 Update chat completion request: {}", completion_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<CreateChatCompletionResponse> = client
+        .post(&format!("/chat/completions/{}", completion_id), &request)
+        .await?;
+
+    info!("✅ Chat completion обновлен");
+    Ok(response)
+}
+
+/// Удаляет сохраненный chat completion.
+///
+/// # Arguments
+///
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - HTTP заголовки запроса, содержащие Authorization токен
+/// * `completion_id` - Идентификатор chat completion для удаления
+///
+/// # Returns
+///
+/// * `Ok(JsonReply<ChatCompletionDeleted>)` - Подтверждение удаления
+/// * `Err(AppError)` - Ошибка при выполнении запроса
+pub async fn delete_chat_completion(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(completion_id): Path<String>,
+) -> Result<JsonReply<ChatCompletionDeleted>, AppError> {
+    info!("💬 Delete chat completion request: {}", completion_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<ChatCompletionDeleted> = client
+        .delete(&format!("/chat/completions/{}", completion_id))
+        .await?;
+
+    info!("✅ Chat completion удален");
+    Ok(response)
+}
+
+/// Возвращает сообщения сохраненного chat completion.
+///
+/// # Arguments
+///
+/// * `state` - Состояние приложения с настройками upstream
+/// * `completion_id` - Идентификатор chat completion
+/// * `headers` - HTTP заголовки запроса, содержащие Authorization токен
+/// * `query` - Параметры пагинации (`limit`, `order`, `after`)
+///
+/// # Returns
+///
+/// * `Ok(JsonReply<ChatCompletionMessageList>)` - Страница сообщений (с пагинацией)
+/// * `Err(AppError)` - Ошибка при выполнении запроса
+pub async fn list_chat_completion_messages(
+    State(state): State<Arc<AppState>>,
+    Path(completion_id): Path<String>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ChatCompletionMessageList>, AppError> {
+    info!(
+        "💬 List chat completion messages request: {}",
+        completion_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<ChatCompletionMessageList> = client
+        .get_with_query(
+            &format!("/chat/completions/{}/messages", completion_id),
+            &query,
+        )
+        .await?;
+
+    info!("✅ Chat completion messages list получен");
+    Ok(response)
 }
 
 /// Обработчик для создания legacy text completion.
@@ -68,13 +270,13 @@ pub async fn chat_completions(
 ///
 /// # Arguments
 ///
-/// * `_state` - Состояние приложения (не используется, так как токен передается от клиента)
+/// * `state` - Состояние приложения с настройками upstream
 /// * `headers` - HTTP заголовки запроса, содержащие Authorization токен
 /// * `request` - Параметры запроса для создания text completion
 ///
 /// # Returns
 ///
-/// * `Ok(Json<CreateCompletionResponse>)` - Успешный ответ от OpenAI API
+/// * `Ok(JsonReply<CreateCompletionResponse>)` - Успешный ответ от OpenAI API
 /// * `Err(AppError)` - Ошибка при выполнении запроса
 ///
 /// # Examples
@@ -89,24 +291,21 @@ pub async fn chat_completions(
 ///   }'
 /// ```
 pub async fn completions(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-    Json(request): Json<CreateCompletionRequest>,
-) -> Result<Json<CreateCompletionResponse>, AppError> {
-    info!("📝 Text completion request: model={}", request.model);
+    request: JsonBody<CreateCompletionRequest>,
+) -> Result<JsonReply<CreateCompletionResponse>, AppError> {
+    info!("📝 Text completion request: model={}", request.model());
 
     // Создаем клиента из Authorization заголовка
-    let client = create_client_from_headers(&headers, false)?;
-
-    let response = client
-        .completions()
-        .create(request)
-        .await
-        .map_err(|e| {
-            error!("❌ Text completion error: {}", e);
-            AppError(format!("Text completion error: {}", e))
-        })?;
-
-    info!("✅ Text completion успешно выполнен");
-    Ok(Json(response))
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<CreateCompletionResponse> =
+        client.post("/completions", &request).await?;
+
+    info!(
+        "✅ Text completion успешно выполнен: total_tokens={}",
+        response.total_tokens().unwrap_or_default()
+    );
+    Ok(response)
 }
@@ -0,0 +1,279 @@
+//! Обработчики Conversations API.
+//!
+//! Долговременные разговоры Responses API: создание, получение, изменение и удаление
+//! разговоров, а также управление их элементами (items). Разговор передается в
+//! `POST /v1/responses` через поле `conversation` вместо цепочек `previous_response_id`.
+
+use crate::{
+    error::AppError,
+    payload::{JsonBody, JsonReply},
+    query::Query,
+    routes::ListQuery,
+    state::AppState,
+    utils::create_client_from_headers,
+};
+use async_openai::types::responses::{
+    ConversationItem, ConversationItemList, ConversationResource, CreateConversationItemsRequest,
+    CreateConversationRequest, DeleteConversationResponse, UpdateConversationRequest,
+};
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+};
+use std::sync::Arc;
+use tracing::info;
+
+/// Создает разговор (опционально с начальными элементами `items`).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `CreateConversationRequest` с `metadata` и начальными `items`
+///
+/// # Returns
+/// * `Ok(JsonReply<ConversationResource>)` - Созданный разговор с его `id`
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+///
+/// # Пример
+/// ```bash
+/// curl -X POST http://localhost:8080/v1/conversations \
+///   -H "Content-Type: application/json" \
+///   -H "Authorization: Bearer sk-..." \
+///   -d '{"metadata":{"topic":"demo"},"items":[{"type":"message","role":"user","content":"Hello!"}]}'
+/// ```
+pub async fn create_conversation(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    request: JsonBody<CreateConversationRequest>,
+) -> Result<JsonReply<ConversationResource>, AppError> {
+    info!("🗨️ Create conversation request");
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<ConversationResource> = client.post("/conversations", &request).await?;
+
+    info!("✅ Conversation создан: {}", response.id());
+    Ok(response)
+}
+
+/// Возвращает разговор по `conversation_id`.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `conversation_id` - Идентификатор разговора
+/// * `headers` - Authorization заголовок клиента
+///
+/// # Returns
+/// * `Ok(JsonReply<ConversationResource>)` - Найденный разговор
+/// * `Err(AppError)` - Ошибка запроса или разговор не найден
+pub async fn get_conversation(
+    State(state): State<Arc<AppState>>,
+    Path(conversation_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<JsonReply<ConversationResource>, AppError> {
+    info!("🗨️ Get conversation request: {}", conversation_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<ConversationResource> = client
+        .get(&format!("/conversations/{}", conversation_id))
+        .await?;
+
+    info!("✅ Conversation получен");
+    Ok(response)
+}
+
+/// Обновляет `metadata` разговора.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `conversation_id` - Идентификатор разговора
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `UpdateConversationRequest` с новыми `metadata`
+///
+/// # Returns
+/// * `Ok(JsonReply<ConversationResource>)` - Обновленный разговор
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn update_conversation(
+    State(state): State<Arc<AppState>>,
+    Path(conversation_id): Path<String>,
+    headers: HeaderMap,
+    request: JsonBody<UpdateConversationRequest>,
+) -> Result<JsonReply<ConversationResource>, AppError> {
+    info!("🗨️ Update conversation request: {}", conversation_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<ConversationResource> = client
+        .post(&format!("/conversations/{}", conversation_id), &request)
+        .await?;
+
+    info!("✅ Conversation обновлен");
+    Ok(response)
+}
+
+/// Удаляет разговор (элементы разговора при этом не удаляются).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `conversation_id` - Идентификатор разговора для удаления
+///
+/// # Returns
+/// * `Ok(JsonReply<DeleteConversationResponse>)` - Подтверждение удаления
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn delete_conversation(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(conversation_id): Path<String>,
+) -> Result<JsonReply<DeleteConversationResponse>, AppError> {
+    info!("🗨️ Delete conversation request: {}", conversation_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<DeleteConversationResponse> = client
+        .delete(&format!("/conversations/{}", conversation_id))
+        .await?;
+
+    info!("✅ Conversation удален");
+    Ok(response)
+}
+
+/// Возвращает элементы разговора.
+///
+/// Параметры query string (`limit`, `order`, `after` и повторяющийся `include[]`)
+/// передаются в OpenAI в исходном виде.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `conversation_id` - Идентификатор разговора
+/// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации и `include[]`
+///
+/// # Returns
+/// * `Ok(JsonReply<ConversationItemList>)` - Страница элементов (с пагинацией)
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn list_conversation_items(
+    State(state): State<Arc<AppState>>,
+    Path(conversation_id): Path<String>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ConversationItemList>, AppError> {
+    info!("🗨️ List conversation items request: {}", conversation_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<ConversationItemList> = client
+        .get_with_query(&format!("/conversations/{}/items", conversation_id), &query)
+        .await?;
+
+    info!("✅ Conversation items list получен");
+    Ok(response)
+}
+
+/// Добавляет элементы в разговор (до 20 за запрос).
+///
+/// Query string (например, `include[]`) передается в OpenAI без изменений.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `conversation_id` - Идентификатор разговора
+/// * `query` - Параметры query string (например, `include[]`)
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `CreateConversationItemsRequest` со списком `items`
+///
+/// # Returns
+/// * `Ok(JsonReply<ConversationItemList>)` - Добавленные элементы
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn create_conversation_items(
+    State(state): State<Arc<AppState>>,
+    Path(conversation_id): Path<String>,
+    Query(query): Query<ListQuery>,
+    headers: HeaderMap,
+    request: JsonBody<CreateConversationItemsRequest>,
+) -> Result<JsonReply<ConversationItemList>, AppError> {
+    info!("🗨️ Create conversation items request: {}", conversation_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<ConversationItemList> = client
+        .post_with_query(
+            &format!("/conversations/{}/items", conversation_id),
+            &query,
+            &request,
+        )
+        .await?;
+
+    info!("✅ Conversation items добавлены");
+    Ok(response)
+}
+
+/// Возвращает элемент разговора по `item_id`.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `conversation_id` - Идентификатор разговора
+/// * `item_id` - Идентификатор элемента
+/// * `headers` - Authorization заголовок клиента
+/// * `query` - Повторяющийся параметр `include[]`
+///
+/// # Returns
+/// * `Ok(JsonReply<ConversationItem>)` - Найденный элемент
+/// * `Err(AppError)` - Ошибка запроса или элемент не найден
+pub async fn get_conversation_item(
+    State(state): State<Arc<AppState>>,
+    Path((conversation_id, item_id)): Path<(String, String)>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ConversationItem>, AppError> {
+    info!(
+        "🗨️ Get conversation item request: {} in conversation: {}",
+        item_id, conversation_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<ConversationItem> = client
+        .get_with_query(
+            &format!("/conversations/{}/items/{}", conversation_id, item_id),
+            &query,
+        )
+        .await?;
+
+    info!("✅ Conversation item получен");
+    Ok(response)
+}
+
+/// Удаляет элемент из разговора.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `conversation_id` - Идентификатор разговора
+/// * `item_id` - Идентификатор элемента для удаления
+///
+/// # Returns
+/// * `Ok(JsonReply<ConversationResource>)` - Разговор после удаления элемента
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn delete_conversation_item(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path((conversation_id, item_id)): Path<(String, String)>,
+) -> Result<JsonReply<ConversationResource>, AppError> {
+    info!(
+        "🗨️ Delete conversation item request: {} in conversation: {}",
+        item_id, conversation_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<ConversationResource> = client
+        .delete(&format!(
+            "/conversations/{}/items/{}",
+            conversation_id, item_id
+        ))
+        .await?;
+
+    info!("✅ Conversation item удален");
+    Ok(response)
+}
@@ -2,11 +2,16 @@
 //!
 //! Проксирует запросы к OpenAI Embeddings (например, text-embedding-3-large).
 
-use crate::{error::AppError, state::AppState, utils::create_client_from_headers};
+use crate::{
+    error::AppError,
+    payload::{JsonBody, JsonReply},
+    state::AppState,
+    utils::create_client_from_headers,
+};
 use async_openai::types::embeddings::{CreateEmbeddingRequest, CreateEmbeddingResponse};
-use axum::{extract::State, http::HeaderMap, Json};
+use axum::{extract::State, http::HeaderMap};
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::info;
 
 /// Создает embedding для текста или батча текстов.
 ///
@@ -14,31 +19,27 @@ use tracing::{error, info};
 /// результат без хранения данных на сервере.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `headers` - Authorization заголовок клиента
 /// * `request` - `CreateEmbeddingRequest` с моделью и входными данными
 ///
 /// # Returns
-/// * `Ok(Json<CreateEmbeddingResponse>)` - Векторы эмбеддингов
+/// * `Ok(JsonReply<CreateEmbeddingResponse>)` - Векторы эмбеддингов
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn embeddings(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-    Json(request): Json<CreateEmbeddingRequest>,
-) -> Result<Json<CreateEmbeddingResponse>, AppError> {
-    info!("🔢 Embedding request: model={}", request.model);
+    request: JsonBody<CreateEmbeddingRequest>,
+) -> Result<JsonReply<CreateEmbeddingResponse>, AppError> {
+    info!("🔢 Embedding request: model={}", request.model());
 
-    let client = create_client_from_headers(&headers, false)?;
+    let client = create_client_from_headers(&state, &headers, false)?;
 
-    let response = client
-        .embeddings()
-        .create(request)
-        .await
-        .map_err(|e| {
-            error!("❌ Embedding error: {}", e);
-            AppError(format!("Embedding error: {}", e))
-        })?;
+    let response: JsonReply<CreateEmbeddingResponse> = client.post("/embeddings", &request).await?;
 
-    info!("✅ Embedding успешно выполнен");
-    Ok(Json(response))
+    info!(
+        "✅ Embedding успешно выполнен: total_tokens={}",
+        response.total_tokens().unwrap_or_default()
+    );
+    Ok(response)
 }
@@ -0,0 +1,507 @@
+//! Обработчики Evals API.
+//!
+//! Оценка качества моделей: CRUD evals, запуск и отмена runs, просмотр output items,
+//! а также alpha эндпоинты graders (`/v1/fine_tuning/alpha/graders/*`) для проверки
+//! и пробного запуска graders.
+//!
+//! Типы запросов `async-openai` для evals и graders поддерживают только сериализацию,
+//! поэтому тела запросов описаны локальными структурами: обязательные поля проверяются,
+//! а конфигурации data sources и graders передаются в OpenAI как есть.
+
+use crate::{
+    error::AppError,
+    payload::{JsonBody, JsonReply},
+    query::Query,
+    routes::ListQuery,
+    state::AppState,
+    utils::create_client_from_headers,
+};
+use async_openai::types::evals::{
+    DeleteEvalResponse, DeleteEvalRunResponse, Eval, EvalList, EvalRun, EvalRunList,
+    EvalRunOutputItem, EvalRunOutputItemList,
+};
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::info;
+
+/// Тело запроса `POST /v1/evals`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateEvalParams {
+    /// Название eval
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Конфигурация data source (`custom`, `logs` или `stored_completions`)
+    pub data_source_config: Value,
+    /// Graders, применяемые ко всем runs eval
+    pub testing_criteria: Vec<Value>,
+    /// Произвольные метаданные (до 16 пар ключ-значение)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Value>,
+}
+
+/// Тело запроса `POST /v1/evals/{eval_id}`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UpdateEvalParams {
+    /// Новое название eval
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Новые метаданные eval
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Value>,
+}
+
+/// Тело запроса `POST /v1/evals/{eval_id}/runs`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateEvalRunParams {
+    /// Название run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Data source run (`jsonl`, `completions` или `responses`)
+    pub data_source: Value,
+    /// Произвольные метаданные run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Value>,
+}
+
+/// Тело запроса `POST /v1/fine_tuning/alpha/graders/validate`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ValidateGraderParams {
+    /// Конфигурация grader
+    pub grader: Value,
+}
+
+/// Тело запроса `POST /v1/fine_tuning/alpha/graders/run`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RunGraderParams {
+    /// Конфигурация grader
+    pub grader: Value,
+    /// Ответ модели, который оценивает grader
+    pub model_sample: String,
+    /// Элемент датасета, доступный grader как `item`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item: Option<Value>,
+}
+
+/// Создает eval с конфигурацией data source и testing criteria.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `CreateEvalParams` с `data_source_config` и `testing_criteria`
+///
+/// # Returns
+/// * `Ok(JsonReply<Eval>)` - Созданный eval с его `id`
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+///
+/// # Пример
+/// ```bash
+/// curl -X POST http://localhost:8080/v1/evals \
+///   -H "Content-Type: application/json" \
+///   -H "Authorization: Bearer sk-..." \
+///   -d '{"name":"qa","data_source_config":{"type":"custom","item_schema":{"type":"object"}},"testing_criteria":[{"type":"string_check","name":"match","input":"{{sample.output_text}}","reference":"{{item.answer}}","operation":"eq"}]}'
+/// ```
+pub async fn create_eval(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    request: JsonBody<CreateEvalParams>,
+) -> Result<JsonReply<Eval>, AppError> {
+    info!("🧪 Create eval request");
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<Eval> = client.post("/evals", &request).await?;
+
+    info!("✅ Eval создан: {}", response.id());
+    Ok(response)
+}
+
+/// Возвращает список evals.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации и сортировки (`limit`, `order`, `order_by`, `after`)
+///
+/// # Returns
+/// * `Ok(JsonReply<EvalList>)` - Страница evals (с пагинацией)
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn list_evals(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<EvalList>, AppError> {
+    info!("🧪 List evals request");
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<EvalList> = client.get_with_query("/evals", &query).await?;
+
+    info!("✅ Evals list получен");
+    Ok(response)
+}
+
+/// Возвращает eval по `eval_id`.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `eval_id` - Идентификатор eval
+/// * `headers` - Authorization заголовок клиента
+///
+/// # Returns
+/// * `Ok(JsonReply<Eval>)` - Найденный eval
+/// * `Err(AppError)` - Ошибка запроса или eval не найден
+pub async fn get_eval(
+    State(state): State<Arc<AppState>>,
+    Path(eval_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<JsonReply<Eval>, AppError> {
+    info!("🧪 Get eval request: {}", eval_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<Eval> = client.get(&format!("/evals/{}", eval_id)).await?;
+
+    info!("✅ Eval получен");
+    Ok(response)
+}
+
+/// Обновляет название и `metadata` eval.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `eval_id` - Идентификатор eval
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `UpdateEvalParams` с новыми `name` и `metadata`
+///
+/// # Returns
+/// * `Ok(JsonReply<Eval>)` - Обновленный eval
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn update_eval(
+    State(state): State<Arc<AppState>>,
+    Path(eval_id): Path<String>,
+    headers: HeaderMap,
+    request: JsonBody<UpdateEvalParams>,
+) -> Result<JsonReply<Eval>, AppError> {
+    info!("🧪 Update eval request: {}", eval_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<Eval> = client
+        .post(&format!("/evals/{}", eval_id), &request)
+        .await?;
+
+    info!("✅ Eval обновлен");
+    Ok(response)
+}
+
+/// Удаляет eval.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `eval_id` - Идентификатор eval для удаления
+///
+/// # Returns
+/// * `Ok(JsonReply<DeleteEvalResponse>)` - Подтверждение удаления
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn delete_eval(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(eval_id): Path<String>,
+) -> Result<JsonReply<DeleteEvalResponse>, AppError> {
+    info!("🧪 Delete eval request: {}", eval_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<DeleteEvalResponse> =
+        client.delete(&format!("/evals/{}", eval_id)).await?;
+
+    info!("✅ Eval удален");
+    Ok(response)
+}
+
+/// Запускает eval run на указанном data source.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `eval_id` - Идентификатор eval
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `CreateEvalRunParams` с `data_source`
+///
+/// # Returns
+/// * `Ok(JsonReply<EvalRun>)` - Созданный run с его `id`
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn create_eval_run(
+    State(state): State<Arc<AppState>>,
+    Path(eval_id): Path<String>,
+    headers: HeaderMap,
+    request: JsonBody<CreateEvalRunParams>,
+) -> Result<JsonReply<EvalRun>, AppError> {
+    info!("🧪 Create eval run request for eval: {}", eval_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<EvalRun> = client
+        .post(&format!("/evals/{}/runs", eval_id), &request)
+        .await?;
+
+    info!("✅ Eval run создан: {}", response.id());
+    Ok(response)
+}
+
+/// Возвращает список runs eval.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `eval_id` - Идентификатор eval
+/// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации и фильтр `status`
+///
+/// # Returns
+/// * `Ok(JsonReply<EvalRunList>)` - Страница runs (с пагинацией)
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn list_eval_runs(
+    State(state): State<Arc<AppState>>,
+    Path(eval_id): Path<String>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<EvalRunList>, AppError> {
+    info!("🧪 List eval runs request for eval: {}", eval_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<EvalRunList> = client
+        .get_with_query(&format!("/evals/{}/runs", eval_id), &query)
+        .await?;
+
+    info!("✅ Eval runs list получен");
+    Ok(response)
+}
+
+/// Возвращает eval run по `run_id` (используется для опроса статуса).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `eval_id` - Идентификатор eval
+/// * `run_id` - Идентификатор run
+/// * `headers` - Authorization заголовок клиента
+///
+/// # Returns
+/// * `Ok(JsonReply<EvalRun>)` - Run со статусом и `result_counts`
+/// * `Err(AppError)` - Ошибка запроса или run не найден
+pub async fn get_eval_run(
+    State(state): State<Arc<AppState>>,
+    Path((eval_id, run_id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<JsonReply<EvalRun>, AppError> {
+    info!("🧪 Get eval run request: {} for eval: {}", run_id, eval_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<EvalRun> = client
+        .get(&format!("/evals/{}/runs/{}", eval_id, run_id))
+        .await?;
+
+    info!("✅ Eval run получен");
+    Ok(response)
+}
+
+/// Отменяет выполняющийся eval run.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `eval_id` - Идентификатор eval
+/// * `run_id` - Идентификатор run
+/// * `headers` - Authorization заголовок клиента
+///
+/// # Returns
+/// * `Ok(JsonReply<EvalRun>)` - Run в статусе `canceled`
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn cancel_eval_run(
+    State(state): State<Arc<AppState>>,
+    Path((eval_id, run_id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<JsonReply<EvalRun>, AppError> {
+    info!(
+        "🧪 Cancel eval run request: {} for eval: {}",
+        run_id, eval_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<EvalRun> = client
+        .post_empty(&format!("/evals/{}/runs/{}", eval_id, run_id))
+        .await?;
+
+    info!("✅ Eval run отменен");
+    Ok(response)
+}
+
+/// Удаляет eval run.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `eval_id` - Идентификатор eval
+/// * `run_id` - Идентификатор run для удаления
+///
+/// # Returns
+/// * `Ok(JsonReply<DeleteEvalRunResponse>)` - Подтверждение удаления
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn delete_eval_run(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path((eval_id, run_id)): Path<(String, String)>,
+) -> Result<JsonReply<DeleteEvalRunResponse>, AppError> {
+    info!(
+        "🧪 Delete eval run request: {} for eval: {}",
+        run_id, eval_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<DeleteEvalRunResponse> = client
+        .delete(&format!("/evals/{}/runs/{}", eval_id, run_id))
+        .await?;
+
+    info!("✅ Eval run удален");
+    Ok(response)
+}
+
+/// Возвращает output items eval run.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `eval_id` - Идентификатор eval
+/// * `run_id` - Идентификатор run
+/// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации и фильтр `status` (`pass` или `fail`)
+///
+/// # Returns
+/// * `Ok(JsonReply<EvalRunOutputItemList>)` - Страница output items (с пагинацией)
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn list_eval_run_output_items(
+    State(state): State<Arc<AppState>>,
+    Path((eval_id, run_id)): Path<(String, String)>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<EvalRunOutputItemList>, AppError> {
+    info!(
+        "🧪 List eval run output items request: {} for eval: {}",
+        run_id, eval_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<EvalRunOutputItemList> = client
+        .get_with_query(
+            &format!("/evals/{}/runs/{}/output_items", eval_id, run_id),
+            &query,
+        )
+        .await?;
+
+    info!("✅ Eval run output items list получен");
+    Ok(response)
+}
+
+/// Возвращает output item eval run по `output_item_id`.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `eval_id` - Идентификатор eval
+/// * `run_id` - Идентификатор run
+/// * `output_item_id` - Идентификатор output item
+/// * `headers` - Authorization заголовок клиента
+///
+/// # Returns
+/// * `Ok(JsonReply<EvalRunOutputItem>)` - Output item с результатами graders
+/// * `Err(AppError)` - Ошибка запроса или output item не найден
+pub async fn get_eval_run_output_item(
+    State(state): State<Arc<AppState>>,
+    Path((eval_id, run_id, output_item_id)): Path<(String, String, String)>,
+    headers: HeaderMap,
+) -> Result<JsonReply<EvalRunOutputItem>, AppError> {
+    info!(
+        "🧪 Get eval run output item request: {} for run: {}",
+        output_item_id, run_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<EvalRunOutputItem> = client
+        .get(&format!(
+            "/evals/{}/runs/{}/output_items/{}",
+            eval_id, run_id, output_item_id
+        ))
+        .await?;
+
+    info!("✅ Eval run output item получен");
+    Ok(response)
+}
+
+/// Проверяет конфигурацию grader.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `ValidateGraderParams` с конфигурацией `grader`
+///
+/// # Returns
+/// * `Ok(JsonReply<Value>)` - Проверенная конфигурация grader
+/// * `Err(AppError)` - Ошибка запроса или некорректный grader
+pub async fn validate_grader(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    request: JsonBody<ValidateGraderParams>,
+) -> Result<JsonReply<Value>, AppError> {
+    info!("🧪 Validate grader request");
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<Value> = client
+        .post("/fine_tuning/alpha/graders/validate", &request)
+        .await?;
+
+    info!("✅ Grader проверен");
+    Ok(response)
+}
+
+/// Запускает grader на одном ответе модели.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `RunGraderParams` с `grader`, `model_sample` и `item`
+///
+/// # Returns
+/// * `Ok(JsonReply<Value>)` - Оценка (`reward`) и метаданные выполнения grader
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+///
+/// # Пример
+/// ```bash
+/// curl -X POST http://localhost:8080/v1/fine_tuning/alpha/graders/run \
+///   -H "Content-Type: application/json" \
+///   -H "Authorization: Bearer sk-..." \
+///   -d '{"grader":{"type":"string_check","name":"match","input":"{{sample.output_text}}","reference":"{{item.answer}}","operation":"eq"},"model_sample":"42","item":{"answer":"42"}}'
+/// ```
+pub async fn run_grader(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    request: JsonBody<RunGraderParams>,
+) -> Result<JsonReply<Value>, AppError> {
+    info!("🧪 Run grader request");
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<Value> = client
+        .post("/fine_tuning/alpha/graders/run", &request)
+        .await?;
+
+    info!("✅ Grader выполнен");
+    Ok(response)
+}
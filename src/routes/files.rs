@@ -3,233 +3,165 @@
 //! Принимает токен из Authorization заголовка и проксирует вызовы к OpenAI Files API
 //! без хранения пользовательских данных на сервере.
 
-use crate::{error::AppError, state::AppState, utils::create_client_from_headers};
-use async_openai::types::files::{CreateFileRequest, DeleteFileResponse, FilePurpose, ListFilesResponse, OpenAIFile, FileInput};
-use async_openai::types::InputSource;
+use crate::{
+    error::AppError, multipart::MultipartForm, payload::JsonReply, query::Query, routes::ListQuery,
+    state::AppState, utils::create_client_from_headers,
+};
+use async_openai::types::files::{DeleteFileResponse, ListFilesResponse, OpenAIFile};
 use axum::{
-    extract::{Multipart, Path, State},
+    extract::{Path, State},
     http::HeaderMap,
-    Json,
+    response::Response,
 };
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::info;
 
 /// Загружает файл в OpenAI Files API через multipart/form-data.
 ///
-/// Ожидает поля `file` (binary) и `purpose` (`assistants` или `fine-tune`). Токен
-/// берется из Authorization заголовка клиента. По умолчанию purpose `assistants`.
+/// Ожидает поля `file` (binary) и `purpose` (`assistants`, `batch`, `fine-tune`, `vision`,
+/// `user_data`, `evals`). Форма пересылается в OpenAI без изменений, включая `purpose`
+/// и необязательные поля `expires_after[anchor]`, `expires_after[seconds]`.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `headers` - Authorization заголовок клиента
-/// * `multipart` - Поля multipart/form-data (`file`, `purpose`)
+/// * `form` - Поля multipart/form-data (`file`, `purpose`)
 ///
 /// # Returns
-/// * `Ok(Json<OpenAIFile>)` - Метаданные загруженного файла
+/// * `Ok(JsonReply<OpenAIFile>)` - Метаданные загруженного файла
 /// * `Err(AppError)` - Ошибка чтения multipart или запроса к OpenAI
 ///
 /// # Пример
 /// ```bash
 /// curl -X POST http://localhost:8080/v1/files \
 ///   -H "Authorization: Bearer sk-..." \
-///   -F "purpose=assistants" \
-///   -F "file=@./myfile.txt"
+///   -F "purpose=batch" \
+///   -F "file=@./requests.jsonl"
 /// ```
 pub async fn upload_file(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-    mut multipart: Multipart,
-) -> Result<Json<OpenAIFile>, AppError> {
-    info!("📁 Upload file request");
-
-    let mut file_bytes: Option<Vec<u8>> = None;
-    let mut filename: Option<String> = None;
-    let mut purpose: Option<String> = None;
-
-    while let Some(field) = multipart
-        .next_field()
-        .await
-        .map_err(|e| AppError(format!("Multipart error: {}", e)))?
-    {
-        let name = field.name().unwrap_or("").to_string();
-
-        match name.as_str() {
-            "file" => {
-                filename = field.file_name().map(|s| s.to_string());
-                file_bytes = Some(
-                    field
-                        .bytes()
-                        .await
-                        .map_err(|e| AppError(format!("File read error: {}", e)))?
-                        .to_vec(),
-                );
-            }
-            "purpose" => {
-                purpose = Some(
-                    field
-                        .text()
-                        .await
-                        .map_err(|e| AppError(format!("Purpose read error: {}", e)))?,
-                );
-            }
-            _ => {}
-        }
+    form: MultipartForm,
+) -> Result<JsonReply<OpenAIFile>, AppError> {
+    if !form.has_file("file") {
+        return Err(AppError::MissingField("file"));
     }
+    let purpose = form
+        .text("purpose")
+        .ok_or(AppError::MissingField("purpose"))?;
 
-    let file_bytes = file_bytes.ok_or_else(|| AppError("File not provided".to_string()))?;
-    let filename = filename.ok_or_else(|| AppError("Filename not provided".to_string()))?;
-    let purpose = purpose.ok_or_else(|| AppError("Purpose not provided".to_string()))?;
-
-    let client = create_client_from_headers(&headers, false)?;
-
-    let file_purpose = match purpose.as_str() {
-        "assistants" => FilePurpose::Assistants,
-        "fine-tune" => FilePurpose::FineTune,
-        _ => FilePurpose::Assistants,
-    };
-
-    let file_request = CreateFileRequest {
-        file: FileInput { 
-            source: InputSource::Bytes { 
-                filename, 
-                bytes: file_bytes.into() 
-            } 
-        },
-        purpose: file_purpose,
-        expires_after: None,
-    };
+    info!("📁 Upload file request: purpose={}", purpose);
 
-    let response = client
-        .files()
-        .create(file_request)
-        .await
-        .map_err(|e| {
-            error!("❌ Upload file error: {}", e);
-            AppError(format!("Upload file error: {}", e))
-        })?;
-
-    info!("✅ File загружен: {}", response.id);
-    Ok(Json(response))
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<OpenAIFile> = client.post_multipart("/files", form.into_form()).await?;
+
+    info!("✅ File загружен: {}", response.id());
+    Ok(response)
 }
 
 /// Возвращает список файлов в аккаунте пользователя OpenAI.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации (`limit` до 10000, `order`, `after`) и фильтр `purpose`
 ///
 /// # Returns
-/// * `Ok(Json<ListFilesResponse>)` - Список файлов (с пагинацией)
+/// * `Ok(JsonReply<ListFilesResponse>)` - Список файлов (с пагинацией)
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn list_files(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-) -> Result<Json<ListFilesResponse>, AppError> {
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ListFilesResponse>, AppError> {
     info!("📁 List files request");
 
-    let client = create_client_from_headers(&headers, false)?;
+    let client = create_client_from_headers(&state, &headers, false)?;
 
-    let response = client.files().list().await.map_err(|e| {
-        error!("❌ List files error: {}", e);
-        AppError(format!("List files error: {}", e))
-    })?;
+    let response: JsonReply<ListFilesResponse> = client.get_with_query("/files", &query).await?;
 
     info!("✅ Files list получен");
-    Ok(Json(response))
+    Ok(response)
 }
 
 /// Возвращает метаданные файла по его `file_id`.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `file_id` - Идентификатор файла
 /// * `headers` - Authorization заголовок клиента
 ///
 /// # Returns
-/// * `Ok(Json<OpenAIFile>)` - Метаданные файла
+/// * `Ok(JsonReply<OpenAIFile>)` - Метаданные файла
 /// * `Err(AppError)` - Ошибка запроса или файл не найден
 pub async fn get_file(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path(file_id): Path<String>,
     headers: HeaderMap,
-) -> Result<Json<OpenAIFile>, AppError> {
+) -> Result<JsonReply<OpenAIFile>, AppError> {
     info!("📁 Get file request: {}", file_id);
 
-    let client = create_client_from_headers(&headers, false)?;
+    let client = create_client_from_headers(&state, &headers, false)?;
 
-    let response = client
-        .files()
-        .retrieve(&file_id)
-        .await
-        .map_err(|e| {
-            error!("❌ Get file error: {}", e);
-            AppError(format!("Get file error: {}", e))
-        })?;
+    let response: JsonReply<OpenAIFile> = client.get(&format!("/files/{}", file_id)).await?;
 
     info!("✅ File получен");
-    Ok(Json(response))
+    Ok(response)
 }
 
 /// Удаляет файл по `file_id`.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `headers` - Authorization заголовок клиента
 /// * `file_id` - Идентификатор файла для удаления
 ///
 /// # Returns
-/// * `Ok(Json<DeleteFileResponse>)` - Подтверждение удаления
+/// * `Ok(JsonReply<DeleteFileResponse>)` - Подтверждение удаления
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn delete_file(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path(file_id): Path<String>,
-) -> Result<Json<DeleteFileResponse>, AppError> {
+) -> Result<JsonReply<DeleteFileResponse>, AppError> {
     info!("📁 Delete file request: {}", file_id);
 
-    let client = create_client_from_headers(&headers, false)?;
+    let client = create_client_from_headers(&state, &headers, false)?;
 
-    let response = client
-        .files()
-        .delete(&file_id)
-        .await
-        .map_err(|e| {
-            error!("❌ Delete file error: {}", e);
-            AppError(format!("Delete file error: {}", e))
-        })?;
+    let response: JsonReply<DeleteFileResponse> =
+        client.delete(&format!("/files/{}", file_id)).await?;
 
     info!("✅ File удален");
-    Ok(Json(response))
+    Ok(response)
 }
 
-/// Возвращает содержимое файла байтами.
+/// Возвращает содержимое файла.
+///
+/// Тело ответа OpenAI передается клиенту потоком, без буферизации в памяти прокси,
+/// с исходными `Content-Type`, `Content-Length` и `Content-Disposition`.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `file_id` - Идентификатор файла
 /// * `headers` - Authorization заголовок клиента
 ///
 /// # Returns
-/// * `Ok(Vec<u8>)` - Байтовое содержимое файла
+/// * `Ok(Response)` - Содержимое файла с заголовками OpenAI (`x-request-id`, лимиты)
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn get_file_content(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path(file_id): Path<String>,
     headers: HeaderMap,
-) -> Result<Vec<u8>, AppError> {
+) -> Result<Response, AppError> {
     info!("📁 Get file content request: {}", file_id);
 
-    let client = create_client_from_headers(&headers, false)?;
+    let client = create_client_from_headers(&state, &headers, false)?;
 
-    let bytes = client
-        .files()
-        .content(&file_id)
-        .await
-        .map_err(|e| {
-            error!("❌ Get file content error: {}", e);
-            AppError(format!("Get file content error: {}", e))
-        })?;
+    let response = client
+        .get_relay(&format!("/files/{}/content", file_id))
+        .await?;
 
-    info!("✅ File content получен: {} bytes", bytes.len());
-    Ok(bytes.to_vec())
+    info!("✅ File content получен");
+    Ok(response)
 }
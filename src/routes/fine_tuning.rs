@@ -0,0 +1,363 @@
+//! Обработчики Fine-tuning API.
+//!
+//! Жизненный цикл fine-tuning jobs (создание, листинг, опрос, отмена, пауза, возобновление),
+//! события и checkpoints задач, а также доступ проектов к checkpoints (permissions).
+
+use crate::{
+    error::AppError,
+    payload::{JsonBody, JsonReply},
+    query::Query,
+    routes::ListQuery,
+    state::AppState,
+    utils::create_client_from_headers,
+};
+use async_openai::types::finetuning::{
+    CreateFineTuningCheckpointPermissionRequest, CreateFineTuningJobRequest,
+    DeleteFineTuningCheckpointPermissionResponse, FineTuningJob,
+    ListFineTuningCheckpointPermissionResponse, ListFineTuningJobCheckpointsResponse,
+    ListFineTuningJobEventsResponse, ListPaginatedFineTuningJobsResponse,
+};
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+};
+use std::sync::Arc;
+use tracing::info;
+
+/// Создает fine-tuning job из загруженного обучающего файла (purpose `fine-tune`).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `CreateFineTuningJobRequest` с моделью, файлами и методом обучения
+///
+/// # Returns
+/// * `Ok(JsonReply<FineTuningJob>)` - Созданная задача с ее `id`
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+///
+/// # Пример
+/// ```bash
+/// curl -X POST http://localhost:8080/v1/fine_tuning/jobs \
+///   -H "Content-Type: application/json" \
+///   -H "Authorization: Bearer sk-..." \
+///   -d '{"model":"gpt-4o-mini-2024-07-18","training_file":"file-abc123"}'
+/// ```
+pub async fn create_fine_tuning_job(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    request: JsonBody<CreateFineTuningJobRequest>,
+) -> Result<JsonReply<FineTuningJob>, AppError> {
+    info!(
+        "🎯 Create fine-tuning job request: model={}",
+        request.model()
+    );
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<FineTuningJob> = client.post("/fine_tuning/jobs", &request).await?;
+
+    info!("✅ Fine-tuning job создан: {}", response.id());
+    Ok(response)
+}
+
+/// Возвращает список fine-tuning jobs.
+///
+/// Параметры query string (`after`, `limit` и фильтры `metadata[key]=value`) передаются
+/// в OpenAI в исходном виде.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации и фильтры по metadata
+///
+/// # Returns
+/// * `Ok(JsonReply<ListPaginatedFineTuningJobsResponse>)` - Страница задач (с пагинацией)
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn list_fine_tuning_jobs(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ListPaginatedFineTuningJobsResponse>, AppError> {
+    info!("🎯 List fine-tuning jobs request");
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<ListPaginatedFineTuningJobsResponse> =
+        client.get_with_query("/fine_tuning/jobs", &query).await?;
+
+    info!("✅ Fine-tuning jobs list получен");
+    Ok(response)
+}
+
+/// Возвращает fine-tuning job по `fine_tuning_job_id` (используется для опроса статуса).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `fine_tuning_job_id` - Идентификатор задачи
+/// * `headers` - Authorization заголовок клиента
+///
+/// # Returns
+/// * `Ok(JsonReply<FineTuningJob>)` - Задача со статусом и `fine_tuned_model`
+/// * `Err(AppError)` - Ошибка запроса или задача не найдена
+pub async fn get_fine_tuning_job(
+    State(state): State<Arc<AppState>>,
+    Path(fine_tuning_job_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<JsonReply<FineTuningJob>, AppError> {
+    info!("🎯 Get fine-tuning job request: {}", fine_tuning_job_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<FineTuningJob> = client
+        .get(&format!("/fine_tuning/jobs/{}", fine_tuning_job_id))
+        .await?;
+
+    info!("✅ Fine-tuning job получен");
+    Ok(response)
+}
+
+/// Отменяет fine-tuning job.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `fine_tuning_job_id` - Идентификатор задачи
+/// * `headers` - Authorization заголовок клиента
+///
+/// # Returns
+/// * `Ok(JsonReply<FineTuningJob>)` - Отмененная задача
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn cancel_fine_tuning_job(
+    State(state): State<Arc<AppState>>,
+    Path(fine_tuning_job_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<JsonReply<FineTuningJob>, AppError> {
+    info!("🎯 Cancel fine-tuning job request: {}", fine_tuning_job_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<FineTuningJob> = client
+        .post_empty(&format!("/fine_tuning/jobs/{}/cancel", fine_tuning_job_id))
+        .await?;
+
+    info!("✅ Fine-tuning job отменен");
+    Ok(response)
+}
+
+/// Приостанавливает выполняющийся fine-tuning job.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `fine_tuning_job_id` - Идентификатор задачи
+/// * `headers` - Authorization заголовок клиента
+///
+/// # Returns
+/// * `Ok(JsonReply<FineTuningJob>)` - Приостановленная задача
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn pause_fine_tuning_job(
+    State(state): State<Arc<AppState>>,
+    Path(fine_tuning_job_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<JsonReply<FineTuningJob>, AppError> {
+    info!("🎯 Pause fine-tuning job request: {}", fine_tuning_job_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<FineTuningJob> = client
+        .post_empty(&format!("/fine_tuning/jobs/{}/pause", fine_tuning_job_id))
+        .await?;
+
+    info!("✅ Fine-tuning job приостановлен");
+    Ok(response)
+}
+
+/// Возобновляет приостановленный fine-tuning job.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `fine_tuning_job_id` - Идентификатор задачи
+/// * `headers` - Authorization заголовок клиента
+///
+/// # Returns
+/// * `Ok(JsonReply<FineTuningJob>)` - Возобновленная задача
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn resume_fine_tuning_job(
+    State(state): State<Arc<AppState>>,
+    Path(fine_tuning_job_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<JsonReply<FineTuningJob>, AppError> {
+    info!("🎯 Resume fine-tuning job request: {}", fine_tuning_job_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<FineTuningJob> = client
+        .post_empty(&format!("/fine_tuning/jobs/{}/resume", fine_tuning_job_id))
+        .await?;
+
+    info!("✅ Fine-tuning job возобновлен");
+    Ok(response)
+}
+
+/// Возвращает события fine-tuning job (ход обучения, метрики, ошибки).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `fine_tuning_job_id` - Идентификатор задачи
+/// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации (`limit`, `after`)
+///
+/// # Returns
+/// * `Ok(JsonReply<ListFineTuningJobEventsResponse>)` - Страница событий (с пагинацией)
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn list_fine_tuning_events(
+    State(state): State<Arc<AppState>>,
+    Path(fine_tuning_job_id): Path<String>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ListFineTuningJobEventsResponse>, AppError> {
+    info!("🎯 List fine-tuning events request: {}", fine_tuning_job_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<ListFineTuningJobEventsResponse> = client
+        .get_with_query(
+            &format!("/fine_tuning/jobs/{}/events", fine_tuning_job_id),
+            &query,
+        )
+        .await?;
+
+    info!("✅ Fine-tuning events list получен");
+    Ok(response)
+}
+
+/// Возвращает checkpoints fine-tuning job.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `fine_tuning_job_id` - Идентификатор задачи
+/// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации (`limit`, `after`)
+///
+/// # Returns
+/// * `Ok(JsonReply<ListFineTuningJobCheckpointsResponse>)` - Страница checkpoints (с пагинацией)
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn list_fine_tuning_checkpoints(
+    State(state): State<Arc<AppState>>,
+    Path(fine_tuning_job_id): Path<String>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ListFineTuningJobCheckpointsResponse>, AppError> {
+    info!(
+        "🎯 List fine-tuning checkpoints request: {}",
+        fine_tuning_job_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<ListFineTuningJobCheckpointsResponse> = client
+        .get_with_query(
+            &format!("/fine_tuning/jobs/{}/checkpoints", fine_tuning_job_id),
+            &query,
+        )
+        .await?;
+
+    info!("✅ Fine-tuning checkpoints list получен");
+    Ok(response)
+}
+
+/// Открывает проектам доступ к checkpoint дообученной модели (требуется admin ключ).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `checkpoint` - Идентификатор checkpoint дообученной модели
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `CreateFineTuningCheckpointPermissionRequest` со списком `project_ids`
+///
+/// # Returns
+/// * `Ok(JsonReply<ListFineTuningCheckpointPermissionResponse>)` - Созданные разрешения
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn create_checkpoint_permissions(
+    State(state): State<Arc<AppState>>,
+    Path(checkpoint): Path<String>,
+    headers: HeaderMap,
+    request: JsonBody<CreateFineTuningCheckpointPermissionRequest>,
+) -> Result<JsonReply<ListFineTuningCheckpointPermissionResponse>, AppError> {
+    info!("🎯 Create checkpoint permissions request: {}", checkpoint);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<ListFineTuningCheckpointPermissionResponse> = client
+        .post(
+            &format!("/fine_tuning/checkpoints/{}/permissions", checkpoint),
+            &request,
+        )
+        .await?;
+
+    info!("✅ Checkpoint permissions созданы");
+    Ok(response)
+}
+
+/// Возвращает разрешения доступа к checkpoint дообученной модели (требуется admin ключ).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `checkpoint` - Идентификатор checkpoint дообученной модели
+/// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации и фильтр `project_id`
+///
+/// # Returns
+/// * `Ok(JsonReply<ListFineTuningCheckpointPermissionResponse>)` - Страница разрешений
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn list_checkpoint_permissions(
+    State(state): State<Arc<AppState>>,
+    Path(checkpoint): Path<String>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ListFineTuningCheckpointPermissionResponse>, AppError> {
+    info!("🎯 List checkpoint permissions request: {}", checkpoint);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<ListFineTuningCheckpointPermissionResponse> = client
+        .get_with_query(
+            &format!("/fine_tuning/checkpoints/{}/permissions", checkpoint),
+            &query,
+        )
+        .await?;
+
+    info!("✅ Checkpoint permissions list получен");
+    Ok(response)
+}
+
+/// Отзывает разрешение доступа к checkpoint дообученной модели (требуется admin ключ).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `checkpoint` - Идентификатор checkpoint дообученной модели
+/// * `permission_id` - Идентификатор разрешения для удаления
+///
+/// # Returns
+/// * `Ok(JsonReply<DeleteFineTuningCheckpointPermissionResponse>)` - Подтверждение удаления
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn delete_checkpoint_permission(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path((checkpoint, permission_id)): Path<(String, String)>,
+) -> Result<JsonReply<DeleteFineTuningCheckpointPermissionResponse>, AppError> {
+    info!(
+        "🎯 Delete checkpoint permission request: {} for checkpoint: {}",
+        permission_id, checkpoint
+    );
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<DeleteFineTuningCheckpointPermissionResponse> = client
+        .delete(&format!(
+            "/fine_tuning/checkpoints/{}/permissions/{}",
+            checkpoint, permission_id
+        ))
+        .await?;
+
+    info!("✅ Checkpoint permission удален");
+    Ok(response)
+}
@@ -1,41 +1,141 @@
 //! Обработчики DALL-E (Images API).
 //!
-//! Проксирует запросы генерации изображений к OpenAI Images API.
+//! Проксирует запросы генерации, редактирования и вариаций изображений к OpenAI Images API.
+//! Генерация и редактирование поддерживают потоковый режим (SSE) с частичными изображениями
+//! для gpt-image моделей.
 
-use crate::{error::AppError, state::AppState, utils::create_client_from_headers};
+use crate::{
+    error::AppError,
+    multipart::MultipartForm,
+    payload::{JsonBody, JsonReply},
+    state::AppState,
+    utils::create_client_from_headers,
+};
 use async_openai::types::images::{CreateImageRequest, ImagesResponse};
-use axum::{extract::State, http::HeaderMap, Json};
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+};
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::info;
 
 /// Генерирует изображение по текстовому описанию через OpenAI Images API.
 ///
+/// При `"stream": true` события `image_generation.partial_image` (количество задается
+/// `partial_images`) и `image_generation.completed` ретранслируются клиенту через SSE.
+///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `headers` - Authorization заголовок клиента
 /// * `request` - `CreateImageRequest` с prompt/параметрами генерации
 ///
 /// # Returns
-/// * `Ok(Json<ImagesResponse>)` - Сгенерированные изображения/ссылки/base64
+/// * `Ok(Response)` - Сгенерированные изображения/ссылки/base64 (JSON) или SSE-поток событий
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn create_image(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-    Json(request): Json<CreateImageRequest>,
-) -> Result<Json<ImagesResponse>, AppError> {
-    info!("🎨 Image generation request");
+    request: JsonBody<CreateImageRequest>,
+) -> Result<Response, AppError> {
+    info!("🎨 Image generation request: model={}", request.model());
 
-    let client = create_client_from_headers(&headers, false)?;
+    let client = create_client_from_headers(&state, &headers, false)?;
 
-    let response = client
-        .images()
-        .generate(request)
-        .await
-        .map_err(|e| {
-            error!("❌ Image generation error: {}", e);
-            AppError(format!("Image generation error: {}", e))
-        })?;
+    if request.stream() {
+        let response = client.post_stream("/images/generations", &request).await?;
+
+        info!("✅ Image generation stream открыт");
+        return Ok(response);
+    }
+
+    let response: JsonReply<ImagesResponse> = client.post("/images/generations", &request).await?;
 
     info!("✅ Image успешно сгенерирован");
-    Ok(Json(response))
+    Ok(response.into_response())
+}
+
+/// Редактирует изображения по текстовому описанию через OpenAI Images API.
+///
+/// Принимает одно (`image`) или несколько (`image[]`) исходных изображений и
+/// необязательную маску (`mask`). Все поля формы передаются в OpenAI без изменений,
+/// при `stream=true` частичные изображения ретранслируются через SSE.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `form` - Поля multipart/form-data (обязательны `image`/`image[]` и `prompt`)
+///
+/// # Returns
+/// * `Ok(Response)` - Отредактированные изображения (JSON) или SSE-поток событий
+/// * `Err(AppError)` - Ошибка чтения multipart или запроса к OpenAI
+///
+/// # Пример
+/// ```bash
+/// curl -X POST http://localhost:8080/v1/images/edits \
+///   -H "Authorization: Bearer sk-..." \
+///   -F "model=gpt-image-1" \
+///   -F "prompt=Put the cat on the sofa" \
+///   -F "image[]=@./cat.png" \
+///   -F "image[]=@./sofa.png" \
+///   -F "stream=true" \
+///   -F "partial_images=2"
+/// ```
+pub async fn create_image_edit(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    form: MultipartForm,
+) -> Result<Response, AppError> {
+    if !form.has_file("image") && !form.has_file("image[]") {
+        return Err(AppError::MissingField("image"));
+    }
+    if form.text("prompt").is_none() {
+        return Err(AppError::MissingField("prompt"));
+    }
+
+    info!(
+        "🎨 Image edit request: model={}, stream={}",
+        form.text("model").unwrap_or_default(),
+        form.stream()
+    );
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response = client
+        .post_multipart_relay("/images/edits", form.into_form())
+        .await?;
+
+    info!("✅ Image edit выполнен");
+    Ok(response)
+}
+
+/// Создает вариации изображения через OpenAI Images API (DALL-E 2).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `form` - Поля multipart/form-data (обязательно `image`)
+///
+/// # Returns
+/// * `Ok(Response)` - Вариации изображения (JSON)
+/// * `Err(AppError)` - Ошибка чтения multipart или запроса к OpenAI
+pub async fn create_image_variation(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    form: MultipartForm,
+) -> Result<Response, AppError> {
+    if !form.has_file("image") {
+        return Err(AppError::MissingField("image"));
+    }
+
+    info!("🎨 Image variation request");
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response = client
+        .post_multipart_relay("/images/variations", form.into_form())
+        .await?;
+
+    info!("✅ Image variation создана");
+    Ok(response)
 }
@@ -1,159 +1,178 @@
 //! Обработчики Messages API (Assistants v2).
 //!
-//! Создание, получение, изменение и листинг сообщений внутри thread.
-
-use crate::{error::AppError, state::AppState, utils::create_client_from_headers};
+//! Создание, получение, изменение, удаление и листинг сообщений внутри thread.
+
+use crate::{
+    error::AppError,
+    payload::{JsonBody, JsonReply},
+    query::Query,
+    routes::ListQuery,
+    state::AppState,
+    utils::create_client_from_headers,
+};
 use async_openai::types::assistants::{
-    CreateMessageRequest, ListMessagesResponse, MessageObject, ModifyMessageRequest,
+    CreateMessageRequest, DeleteMessageResponse, ListMessagesResponse, MessageObject,
+    ModifyMessageRequest,
 };
 use axum::{
     extract::{Path, State},
     http::HeaderMap,
-    Json,
 };
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::info;
 
 /// Создает сообщение внутри thread.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `thread_id` - Идентификатор thread, в котором создается сообщение
 /// * `headers` - Authorization заголовок клиента
 /// * `request` - `CreateMessageRequest` с содержимым сообщения
 ///
 /// # Returns
-/// * `Ok(Json<MessageObject>)` - Созданное сообщение
+/// * `Ok(JsonReply<MessageObject>)` - Созданное сообщение
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn create_message(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path(thread_id): Path<String>,
     headers: HeaderMap,
-    Json(request): Json<CreateMessageRequest>,
-) -> Result<Json<MessageObject>, AppError> {
+    request: JsonBody<CreateMessageRequest>,
+) -> Result<JsonReply<MessageObject>, AppError> {
     info!("💭 Create message request in thread: {}", thread_id);
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .threads()
-        .messages(&thread_id)
-        .create(request)
-        .await
-        .map_err(|e| {
-            error!("❌ Create message error: {}", e);
-            AppError(format!("Create message error: {}", e))
-        })?;
+    let response: JsonReply<MessageObject> = client
+        .post(&format!("/threads/{}/messages", thread_id), &request)
+        .await?;
 
-    info!("✅ Message создано: {}", response.id);
-    Ok(Json(response))
+    info!("✅ Message создано: {}", response.id());
+    Ok(response)
 }
 
 /// Возвращает список сообщений в thread.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `thread_id` - Идентификатор thread
 /// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации и фильтр `run_id`
 ///
 /// # Returns
-/// * `Ok(Json<ListMessagesResponse>)` - Список сообщений (с пагинацией)
+/// * `Ok(JsonReply<ListMessagesResponse>)` - Список сообщений (с пагинацией)
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn list_messages(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path(thread_id): Path<String>,
     headers: HeaderMap,
-) -> Result<Json<ListMessagesResponse>, AppError> {
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ListMessagesResponse>, AppError> {
     info!("💭 List messages request in thread: {}", thread_id);
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .threads()
-        .messages(&thread_id)
-        .list()
-        .await
-        .map_err(|e| {
-            error!("❌ List messages error: {}", e);
-            AppError(format!("List messages error: {}", e))
-        })?;
+    let response: JsonReply<ListMessagesResponse> = client
+        .get_with_query(&format!("/threads/{}/messages", thread_id), &query)
+        .await?;
 
     info!("✅ Messages list получен");
-    Ok(Json(response))
+    Ok(response)
 }
 
 /// Возвращает конкретное сообщение по `message_id` в рамках thread.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `thread_id` - Идентификатор thread
 /// * `message_id` - Идентификатор сообщения
 /// * `headers` - Authorization заголовок клиента
 ///
 /// # Returns
-/// * `Ok(Json<MessageObject>)` - Найденное сообщение
+/// * `Ok(JsonReply<MessageObject>)` - Найденное сообщение
 /// * `Err(AppError)` - Ошибка запроса или сообщение не найдено
 pub async fn get_message(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path((thread_id, message_id)): Path<(String, String)>,
     headers: HeaderMap,
-) -> Result<Json<MessageObject>, AppError> {
+) -> Result<JsonReply<MessageObject>, AppError> {
     info!(
         "💭 Get message request: {} in thread: {}",
         message_id, thread_id
     );
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .threads()
-        .messages(&thread_id)
-        .retrieve(&message_id)
-        .await
-        .map_err(|e| {
-            error!("❌ Get message error: {}", e);
-            AppError(format!("Get message error: {}", e))
-        })?;
+    let response: JsonReply<MessageObject> = client
+        .get(&format!("/threads/{}/messages/{}", thread_id, message_id))
+        .await?;
 
     info!("✅ Message получено");
-    Ok(Json(response))
+    Ok(response)
 }
 
 /// Обновляет сообщение по `message_id` в рамках thread.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `thread_id` - Идентификатор thread
 /// * `message_id` - Идентификатор сообщения
 /// * `headers` - Authorization заголовок клиента
 /// * `request` - `ModifyMessageRequest` с изменениями
 ///
 /// # Returns
-/// * `Ok(Json<MessageObject>)` - Обновленное сообщение
+/// * `Ok(JsonReply<MessageObject>)` - Обновленное сообщение
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn modify_message(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path((thread_id, message_id)): Path<(String, String)>,
     headers: HeaderMap,
-    Json(request): Json<ModifyMessageRequest>,
-) -> Result<Json<MessageObject>, AppError> {
+    request: JsonBody<ModifyMessageRequest>,
+) -> Result<JsonReply<MessageObject>, AppError> {
     info!(
         "💭 Modify message request: {} in thread: {}",
         message_id, thread_id
     );
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .threads()
-        .messages(&thread_id)
-        .update(&message_id, request)
-        .await
-        .map_err(|e| {
-            error!("❌ Modify message error: {}", e);
-            AppError(format!("Modify message error: {}", e))
-        })?;
+    let response: JsonReply<MessageObject> = client
+        .post(
+            &format!("/threads/{}/messages/{}", thread_id, message_id),
+            &request,
+        )
+        .await?;
 
     info!("✅ Message обновлено");
-    Ok(Json(response))
+    Ok(response)
+}
+
+/// Удаляет сообщение из thread.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `thread_id` - Идентификатор thread
+/// * `message_id` - Идентификатор сообщения для удаления
+///
+/// # Returns
+/// * `Ok(JsonReply<DeleteMessageResponse>)` - Подтверждение удаления
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn delete_message(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path((thread_id, message_id)): Path<(String, String)>,
+) -> Result<JsonReply<DeleteMessageResponse>, AppError> {
+    info!(
+        "💭 Delete message request: {} in thread: {}",
+        message_id, thread_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<DeleteMessageResponse> = client
+        .delete(&format!("/threads/{}/messages/{}", thread_id, message_id))
+        .await?;
+
+    info!("✅ Message удалено");
+    Ok(response)
 }
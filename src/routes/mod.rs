@@ -3,20 +3,42 @@
 //! Содержит все обработчики эндпоинтов и конфигурацию роутера приложения.
 
 pub mod assistants;
+pub mod audio;
+pub mod batches;
 pub mod completions;
+pub mod conversations;
 pub mod embeddings;
+pub mod evals;
 pub mod files;
+pub mod fine_tuning;
 pub mod images;
 pub mod messages;
 pub mod models;
+pub mod moderations;
+pub mod passthrough;
+pub mod realtime;
 pub mod responses;
 pub mod runs;
 pub mod threads;
+pub mod uploads;
+pub mod vector_stores;
 
 use crate::state::AppState;
-use axum::{routing::{delete, get, post}, Router};
+use axum::{
+    extract::DefaultBodyLimit,
+    routing::{delete, get, post},
+    Router,
+};
 use std::sync::Arc;
 
+/// Параметры query string list эндпоинтов OpenAI в исходном виде.
+///
+/// Пагинация (`limit`, `order`, `after`, `before`) и фильтры конкретного эндпоинта
+/// (`purpose`, `run_id`, `status`, `metadata[key]=value`, повторяющийся `include[]`)
+/// передаются в OpenAI без разбора, поэтому авто-пагинация SDK и новые параметры API
+/// работают через прокси без изменений.
+pub type ListQuery = Vec<(String, String)>;
+
 /// Создает и конфигурирует главный роутер приложения.
 ///
 /// Регистрирует все эндпоинты для различных сервисов OpenAI API:
@@ -25,9 +47,21 @@ use std::sync::Arc;
 /// - Embeddings
 /// - Models
 /// - Images (DALL-E)
+/// - Audio (транскрипция, перевод, синтез речи)
+/// - Moderations
 /// - Assistants API (assistants, threads, messages, runs)
 /// - Files API
+/// - Uploads API (загрузка больших файлов частями)
+/// - Batch API
+/// - Fine-tuning API (jobs, events, checkpoints, permissions)
+/// - Vector Stores API (файлы, file batches, поиск)
 /// - Responses API
+/// - Conversations API
+/// - Evals API (runs, output items, graders)
+/// - Realtime API (WebSocket, client secrets)
+///
+/// Запросы к остальным `/v1/*` эндпоинтам (включая неподдерживаемые методы известных
+/// путей) пересылаются в OpenAI без изменений через [`passthrough::passthrough`].
 ///
 /// # Arguments
 ///
@@ -37,66 +71,356 @@ use std::sync::Arc;
 ///
 /// Сконфигурированный `Router` с зарегистрированными маршрутами
 pub fn create_router(state: Arc<AppState>) -> Router {
+    // Части Uploads API (до 64 МБ) имеют собственный лимит
+    let part_body_limit = state.config.server.upload_part_body_limit_bytes;
+    // Файлы Files API (входные файлы Batch API до 200 МБ) имеют собственный лимит
+    let file_body_limit = state.config.server.file_body_limit_bytes;
+
     Router::new()
         // Health check
         .route("/", get(health_check))
         .route("/health", get(health_check))
-        
         // ===== Completions API =====
         .route("/v1/chat/completions", post(completions::chat_completions))
+        .route(
+            "/v1/chat/completions",
+            get(completions::list_chat_completions),
+        )
+        .route(
+            "/v1/chat/completions/{completion_id}",
+            get(completions::get_chat_completion),
+        )
+        .route(
+            "/v1/chat/completions/{completion_id}",
+            post(completions::update_chat_completion),
+        )
+        .route(
+            "/v1/chat/completions/{completion_id}",
+            delete(completions::delete_chat_completion),
+        )
+        .route(
+            "/v1/chat/completions/{completion_id}/messages",
+            get(completions::list_chat_completion_messages),
+        )
         .route("/v1/completions", post(completions::completions))
-        
         // ===== Embeddings =====
         .route("/v1/embeddings", post(embeddings::embeddings))
-        
         // ===== Models =====
         .route("/v1/models", get(models::list_models))
         .route("/v1/models/{model_id}", get(models::get_model))
-        
         // ===== Images =====
         .route("/v1/images/generations", post(images::create_image))
-        
+        .route("/v1/images/edits", post(images::create_image_edit))
+        .route(
+            "/v1/images/variations",
+            post(images::create_image_variation),
+        )
+        // ===== Audio =====
+        .route(
+            "/v1/audio/transcriptions",
+            post(audio::create_transcription),
+        )
+        .route("/v1/audio/translations", post(audio::create_translation))
+        .route("/v1/audio/speech", post(audio::create_speech))
+        // ===== Moderations =====
+        .route("/v1/moderations", post(moderations::create_moderation))
         // ===== Assistants API =====
         .route("/v1/assistants", post(assistants::create_assistant))
         .route("/v1/assistants", get(assistants::list_assistants))
-        .route("/v1/assistants/{assistant_id}", get(assistants::get_assistant))
-        .route("/v1/assistants/{assistant_id}", post(assistants::modify_assistant))
-        .route("/v1/assistants/{assistant_id}", delete(assistants::delete_assistant))
-        
+        .route(
+            "/v1/assistants/{assistant_id}",
+            get(assistants::get_assistant),
+        )
+        .route(
+            "/v1/assistants/{assistant_id}",
+            post(assistants::modify_assistant),
+        )
+        .route(
+            "/v1/assistants/{assistant_id}",
+            delete(assistants::delete_assistant),
+        )
         // ===== Threads API =====
         .route("/v1/threads", post(threads::create_thread))
         .route("/v1/threads/{thread_id}", get(threads::get_thread))
         .route("/v1/threads/{thread_id}", post(threads::modify_thread))
         .route("/v1/threads/{thread_id}", delete(threads::delete_thread))
-        
         // ===== Messages API =====
-        .route("/v1/threads/{thread_id}/messages", post(messages::create_message))
-        .route("/v1/threads/{thread_id}/messages", get(messages::list_messages))
-        .route("/v1/threads/{thread_id}/messages/{message_id}", get(messages::get_message))
-        .route("/v1/threads/{thread_id}/messages/{message_id}", post(messages::modify_message))
-        
+        .route(
+            "/v1/threads/{thread_id}/messages",
+            post(messages::create_message),
+        )
+        .route(
+            "/v1/threads/{thread_id}/messages",
+            get(messages::list_messages),
+        )
+        .route(
+            "/v1/threads/{thread_id}/messages/{message_id}",
+            get(messages::get_message),
+        )
+        .route(
+            "/v1/threads/{thread_id}/messages/{message_id}",
+            post(messages::modify_message),
+        )
+        .route(
+            "/v1/threads/{thread_id}/messages/{message_id}",
+            delete(messages::delete_message),
+        )
         // ===== Runs API =====
         .route("/v1/threads/{thread_id}/runs", post(runs::create_run))
         .route("/v1/threads/{thread_id}/runs", get(runs::list_runs))
         .route("/v1/threads/{thread_id}/runs/{run_id}", get(runs::get_run))
-        .route("/v1/threads/{thread_id}/runs/{run_id}", post(runs::modify_run))
-        .route("/v1/threads/{thread_id}/runs/{run_id}/cancel", post(runs::cancel_run))
-        .route("/v1/threads/{thread_id}/runs/{run_id}/submit_tool_outputs", post(runs::submit_tool_outputs))
+        .route(
+            "/v1/threads/{thread_id}/runs/{run_id}",
+            post(runs::modify_run),
+        )
+        .route(
+            "/v1/threads/{thread_id}/runs/{run_id}/cancel",
+            post(runs::cancel_run),
+        )
+        .route(
+            "/v1/threads/{thread_id}/runs/{run_id}/steps",
+            get(runs::list_run_steps),
+        )
+        .route(
+            "/v1/threads/{thread_id}/runs/{run_id}/steps/{step_id}",
+            get(runs::get_run_step),
+        )
+        .route(
+            "/v1/threads/{thread_id}/runs/{run_id}/submit_tool_outputs",
+            post(runs::submit_tool_outputs),
+        )
         .route("/v1/threads/runs", post(runs::create_thread_and_run))
-        
         // ===== Files API =====
-        .route("/v1/files", post(files::upload_file))
+        .route(
+            "/v1/files",
+            post(files::upload_file).layer(DefaultBodyLimit::max(file_body_limit)),
+        )
         .route("/v1/files", get(files::list_files))
         .route("/v1/files/{file_id}", get(files::get_file))
         .route("/v1/files/{file_id}", delete(files::delete_file))
         .route("/v1/files/{file_id}/content", get(files::get_file_content))
-        
+        // ===== Uploads API =====
+        .route("/v1/uploads", post(uploads::create_upload))
+        .route("/v1/uploads/stream", post(uploads::stream_upload))
+        .route(
+            "/v1/uploads/{upload_id}/parts",
+            post(uploads::add_upload_part).layer(DefaultBodyLimit::max(part_body_limit)),
+        )
+        .route(
+            "/v1/uploads/{upload_id}/complete",
+            post(uploads::complete_upload),
+        )
+        .route(
+            "/v1/uploads/{upload_id}/cancel",
+            post(uploads::cancel_upload),
+        )
+        // ===== Batch API =====
+        .route("/v1/batches", post(batches::create_batch))
+        .route("/v1/batches", get(batches::list_batches))
+        .route("/v1/batches/{batch_id}", get(batches::get_batch))
+        .route("/v1/batches/{batch_id}/cancel", post(batches::cancel_batch))
+        // ===== Fine-tuning API =====
+        .route(
+            "/v1/fine_tuning/jobs",
+            post(fine_tuning::create_fine_tuning_job),
+        )
+        .route(
+            "/v1/fine_tuning/jobs",
+            get(fine_tuning::list_fine_tuning_jobs),
+        )
+        .route(
+            "/v1/fine_tuning/jobs/{fine_tuning_job_id}",
+            get(fine_tuning::get_fine_tuning_job),
+        )
+        .route(
+            "/v1/fine_tuning/jobs/{fine_tuning_job_id}/cancel",
+            post(fine_tuning::cancel_fine_tuning_job),
+        )
+        .route(
+            "/v1/fine_tuning/jobs/{fine_tuning_job_id}/pause",
+            post(fine_tuning::pause_fine_tuning_job),
+        )
+        .route(
+            "/v1/fine_tuning/jobs/{fine_tuning_job_id}/resume",
+            post(fine_tuning::resume_fine_tuning_job),
+        )
+        .route(
+            "/v1/fine_tuning/jobs/{fine_tuning_job_id}/events",
+            get(fine_tuning::list_fine_tuning_events),
+        )
+        .route(
+            "/v1/fine_tuning/jobs/{fine_tuning_job_id}/checkpoints",
+            get(fine_tuning::list_fine_tuning_checkpoints),
+        )
+        .route(
+            "/v1/fine_tuning/checkpoints/{checkpoint}/permissions",
+            post(fine_tuning::create_checkpoint_permissions),
+        )
+        .route(
+            "/v1/fine_tuning/checkpoints/{checkpoint}/permissions",
+            get(fine_tuning::list_checkpoint_permissions),
+        )
+        .route(
+            "/v1/fine_tuning/checkpoints/{checkpoint}/permissions/{permission_id}",
+            delete(fine_tuning::delete_checkpoint_permission),
+        )
+        // ===== Vector Stores API =====
+        .route(
+            "/v1/vector_stores",
+            post(vector_stores::create_vector_store),
+        )
+        .route("/v1/vector_stores", get(vector_stores::list_vector_stores))
+        .route(
+            "/v1/vector_stores/{vector_store_id}",
+            get(vector_stores::get_vector_store),
+        )
+        .route(
+            "/v1/vector_stores/{vector_store_id}",
+            post(vector_stores::modify_vector_store),
+        )
+        .route(
+            "/v1/vector_stores/{vector_store_id}",
+            delete(vector_stores::delete_vector_store),
+        )
+        .route(
+            "/v1/vector_stores/{vector_store_id}/search",
+            post(vector_stores::search_vector_store),
+        )
+        .route(
+            "/v1/vector_stores/{vector_store_id}/files",
+            post(vector_stores::create_vector_store_file),
+        )
+        .route(
+            "/v1/vector_stores/{vector_store_id}/files",
+            get(vector_stores::list_vector_store_files),
+        )
+        .route(
+            "/v1/vector_stores/{vector_store_id}/files/{file_id}",
+            get(vector_stores::get_vector_store_file),
+        )
+        .route(
+            "/v1/vector_stores/{vector_store_id}/files/{file_id}",
+            post(vector_stores::update_vector_store_file),
+        )
+        .route(
+            "/v1/vector_stores/{vector_store_id}/files/{file_id}",
+            delete(vector_stores::delete_vector_store_file),
+        )
+        .route(
+            "/v1/vector_stores/{vector_store_id}/files/{file_id}/content",
+            get(vector_stores::get_vector_store_file_content),
+        )
+        .route(
+            "/v1/vector_stores/{vector_store_id}/file_batches",
+            post(vector_stores::create_vector_store_file_batch),
+        )
+        .route(
+            "/v1/vector_stores/{vector_store_id}/file_batches/{batch_id}",
+            get(vector_stores::get_vector_store_file_batch),
+        )
+        .route(
+            "/v1/vector_stores/{vector_store_id}/file_batches/{batch_id}/cancel",
+            post(vector_stores::cancel_vector_store_file_batch),
+        )
+        .route(
+            "/v1/vector_stores/{vector_store_id}/file_batches/{batch_id}/files",
+            get(vector_stores::list_vector_store_file_batch_files),
+        )
         // ===== Responses API =====
         .route("/v1/responses", post(responses::create_response))
         .route("/v1/responses/{response_id}", get(responses::get_response))
-        .route("/v1/responses/{response_id}", delete(responses::delete_response))
-        .route("/v1/responses/{response_id}/cancel", post(responses::cancel_response))
-        
+        .route(
+            "/v1/responses/{response_id}",
+            delete(responses::delete_response),
+        )
+        .route(
+            "/v1/responses/{response_id}/cancel",
+            post(responses::cancel_response),
+        )
+        .route(
+            "/v1/responses/{response_id}/input_items",
+            get(responses::list_input_items),
+        )
+        .route(
+            "/v1/responses/input_tokens",
+            post(responses::count_input_tokens),
+        )
+        .route("/v1/responses/compact", post(responses::compact_response))
+        // ===== Conversations API =====
+        .route(
+            "/v1/conversations",
+            post(conversations::create_conversation),
+        )
+        .route(
+            "/v1/conversations/{conversation_id}",
+            get(conversations::get_conversation),
+        )
+        .route(
+            "/v1/conversations/{conversation_id}",
+            post(conversations::update_conversation),
+        )
+        .route(
+            "/v1/conversations/{conversation_id}",
+            delete(conversations::delete_conversation),
+        )
+        .route(
+            "/v1/conversations/{conversation_id}/items",
+            get(conversations::list_conversation_items),
+        )
+        .route(
+            "/v1/conversations/{conversation_id}/items",
+            post(conversations::create_conversation_items),
+        )
+        .route(
+            "/v1/conversations/{conversation_id}/items/{item_id}",
+            get(conversations::get_conversation_item),
+        )
+        .route(
+            "/v1/conversations/{conversation_id}/items/{item_id}",
+            delete(conversations::delete_conversation_item),
+        )
+        // ===== Evals API =====
+        .route("/v1/evals", post(evals::create_eval))
+        .route("/v1/evals", get(evals::list_evals))
+        .route("/v1/evals/{eval_id}", get(evals::get_eval))
+        .route("/v1/evals/{eval_id}", post(evals::update_eval))
+        .route("/v1/evals/{eval_id}", delete(evals::delete_eval))
+        .route("/v1/evals/{eval_id}/runs", post(evals::create_eval_run))
+        .route("/v1/evals/{eval_id}/runs", get(evals::list_eval_runs))
+        .route(
+            "/v1/evals/{eval_id}/runs/{run_id}",
+            get(evals::get_eval_run),
+        )
+        .route(
+            "/v1/evals/{eval_id}/runs/{run_id}",
+            post(evals::cancel_eval_run),
+        )
+        .route(
+            "/v1/evals/{eval_id}/runs/{run_id}",
+            delete(evals::delete_eval_run),
+        )
+        .route(
+            "/v1/evals/{eval_id}/runs/{run_id}/output_items",
+            get(evals::list_eval_run_output_items),
+        )
+        .route(
+            "/v1/evals/{eval_id}/runs/{run_id}/output_items/{output_item_id}",
+            get(evals::get_eval_run_output_item),
+        )
+        .route(
+            "/v1/fine_tuning/alpha/graders/validate",
+            post(evals::validate_grader),
+        )
+        .route("/v1/fine_tuning/alpha/graders/run", post(evals::run_grader))
+        // ===== Realtime API =====
+        .route("/v1/realtime", get(realtime::realtime_websocket))
+        .route(
+            "/v1/realtime/client_secrets",
+            post(realtime::create_client_secret),
+        )
+        // ===== Passthrough для остальных /v1/* эндпоинтов =====
+        .fallback(passthrough::passthrough)
+        .method_not_allowed_fallback(passthrough::passthrough)
         .with_state(state)
 }
 
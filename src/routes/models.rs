@@ -2,74 +2,64 @@
 //!
 //! Позволяют получить список доступных моделей и детали конкретной модели.
 
-use crate::{error::AppError, state::AppState, utils::create_client_from_headers};
+use crate::{
+    error::AppError, payload::JsonReply, query::Query, routes::ListQuery, state::AppState,
+    utils::create_client_from_headers,
+};
 use async_openai::types::models::{ListModelResponse, Model};
 use axum::{
     extract::{Path, State},
     http::HeaderMap,
-    Json,
 };
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::info;
 
 /// Возвращает список доступных моделей OpenAI для токена клиента.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры query string, передаваемые в OpenAI без изменений
 ///
 /// # Returns
-/// * `Ok(Json<ListModelResponse>)` - Список моделей
+/// * `Ok(JsonReply<ListModelResponse>)` - Список моделей
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn list_models(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-) -> Result<Json<ListModelResponse>, AppError> {
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ListModelResponse>, AppError> {
     info!("📋 List models request");
 
-    let client = create_client_from_headers(&headers, false)?;
+    let client = create_client_from_headers(&state, &headers, false)?;
 
-    let response = client
-        .models()
-        .list()
-        .await
-        .map_err(|e| {
-            error!("❌ List models error: {}", e);
-            AppError(format!("List models error: {}", e))
-        })?;
+    let response: JsonReply<ListModelResponse> = client.get_with_query("/models", &query).await?;
 
     info!("✅ Models list получен");
-    Ok(Json(response))
+    Ok(response)
 }
 
 /// Возвращает информацию о конкретной модели по её идентификатору.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `model_id` - Идентификатор модели
 /// * `headers` - Authorization заголовок клиента
 ///
 /// # Returns
-/// * `Ok(Json<Model>)` - Детали модели
+/// * `Ok(JsonReply<Model>)` - Детали модели
 /// * `Err(AppError)` - Ошибка запроса или модель не найдена
 pub async fn get_model(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path(model_id): Path<String>,
     headers: HeaderMap,
-) -> Result<Json<Model>, AppError> {
+) -> Result<JsonReply<Model>, AppError> {
     info!("📋 Get model request: {}", model_id);
 
-    let client = create_client_from_headers(&headers, false)?;
+    let client = create_client_from_headers(&state, &headers, false)?;
 
-    let response = client
-        .models()
-        .retrieve(&model_id)
-        .await
-        .map_err(|e| {
-            error!("❌ Get model error: {}", e);
-            AppError(format!("Get model error: {}", e))
-        })?;
+    let response: JsonReply<Model> = client.get(&format!("/models/{}", model_id)).await?;
 
     info!("✅ Model получена");
-    Ok(Json(response))
+    Ok(response)
 }
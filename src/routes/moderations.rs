@@ -0,0 +1,48 @@
+//! Обработчики Moderations API.
+//!
+//! Проксирует проверку текста и изображений моделями модерации OpenAI.
+
+use crate::{
+    error::AppError,
+    payload::{JsonBody, JsonReply},
+    state::AppState,
+    utils::create_client_from_headers,
+};
+use async_openai::types::moderations::{CreateModerationRequest, CreateModerationResponse};
+use axum::{extract::State, http::HeaderMap};
+use std::sync::Arc;
+use tracing::info;
+
+/// Классифицирует ввод по категориям модерации OpenAI.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `CreateModerationRequest` с текстом и/или изображениями
+///
+/// # Returns
+/// * `Ok(JsonReply<CreateModerationResponse>)` - Результаты модерации по каждому входу
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+///
+/// # Пример
+/// ```bash
+/// curl -X POST http://localhost:8080/v1/moderations \
+///   -H "Content-Type: application/json" \
+///   -H "Authorization: Bearer sk-..." \
+///   -d '{"model": "omni-moderation-latest", "input": "Hello!"}'
+/// ```
+pub async fn create_moderation(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    request: JsonBody<CreateModerationRequest>,
+) -> Result<JsonReply<CreateModerationResponse>, AppError> {
+    info!("🛡️ Moderation request: model={}", request.model());
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<CreateModerationResponse> =
+        client.post("/moderations", &request).await?;
+
+    info!("✅ Moderation выполнена");
+    Ok(response)
+}
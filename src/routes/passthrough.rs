@@ -0,0 +1,73 @@
+//! Модуль сквозного проксирования.
+//!
+//! Запросы к `/v1/*`, для которых нет типизированного обработчика (новые или еще
+//! не реализованные API OpenAI), пересылаются в OpenAI без разбора: метод, путь,
+//! query string, заголовки и тело передаются как есть, ответ ретранслируется потоком.
+//!
+//! В отличие от типизированных обработчиков, которые передают только заголовки из
+//! allowlist `forwarding.headers`, passthrough пересылает все заголовки клиента (кроме
+//! hop-by-hop, `Host` и `Authorization`): формат запроса неизвестного эндпоинта прокси
+//! не знает, и `Content-Type`, `Accept` и новые заголовки OpenAI нужны upstream как есть.
+
+use crate::{error::AppError, state::AppState, utils::create_client_from_headers};
+use axum::{
+    extract::{Request, State},
+    response::Response,
+};
+use std::sync::Arc;
+use tracing::info;
+
+/// Обработчик для неизвестных эндпоинтов `/v1/*`.
+///
+/// Регистрируется как fallback роутера, поэтому срабатывает только если ни один
+/// типизированный маршрут не подошел по пути или методу. Тело запроса передается
+/// в OpenAI потоком без изменений (лимит `server.body_limit_bytes` не действует),
+/// тело ответа (включая SSE и бинарные данные) передается клиенту по мере поступления.
+///
+/// # Arguments
+///
+/// * `state` - Состояние приложения с настройками upstream
+/// * `request` - Исходный HTTP запрос клиента
+///
+/// # Returns
+///
+/// * `Ok(Response)` - Ответ OpenAI API с исходными статусом, заголовками и телом
+/// * `Err(AppError)` - Путь вне `/v1` (`UnknownEndpoint`), ошибка авторизации,
+///   или соединения с OpenAI
+///
+/// # Examples
+///
+/// ```bash
+/// curl -X POST http://localhost:8080/v1/containers \
+///   -H "Content-Type: application/json" \
+///   -H "Authorization: Bearer sk-..." \
+///   -d '{"name": "My Container"}'
+/// ```
+pub async fn passthrough(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+) -> Result<Response, AppError> {
+    let uri = request.uri();
+
+    // Путь относительно базового URL upstream (который уже включает /v1)
+    let Some(path) = uri.path().strip_prefix("/v1/") else {
+        return Err(AppError::UnknownEndpoint(uri.path().to_string()));
+    };
+    let path_and_query = match uri.query() {
+        Some(query) => format!("/{}?{}", path, query),
+        None => format!("/{}", path),
+    };
+    let (parts, body) = request.into_parts();
+
+    info!("🔀 Passthrough: {} /v1{}", parts.method, path_and_query);
+
+    // Создаем клиента из Authorization заголовка
+    let client = create_client_from_headers(&state, &parts.headers, false)?;
+
+    let response = client
+        .forward(parts.method, &path_and_query, &parts.headers, body)
+        .await?;
+
+    info!("✅ Passthrough ответ: {}", response.status());
+    Ok(response)
+}
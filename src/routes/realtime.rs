@@ -0,0 +1,248 @@
+//! Обработчики Realtime API.
+//!
+//! `GET /v1/realtime` принимает WebSocket соединение клиента, открывает соединение
+//! с OpenAI от имени ключа клиента и ретранслирует сообщения в обе стороны без разбора
+//! событий. `POST /v1/realtime/client_secrets` выпускает временные ключи для браузерных
+//! и мобильных клиентов, которые подключаются к Realtime API без постоянного ключа.
+
+use crate::{
+    error::AppError,
+    payload::{JsonBody, JsonReply},
+    query::Query,
+    routes::ListQuery,
+    state::AppState,
+    upstream::UpstreamWebSocket,
+    utils::create_client_from_headers,
+    websocket::WebSocketUpgrade,
+};
+use axum::{
+    extract::{
+        ws::{CloseFrame, Message, Utf8Bytes, WebSocket},
+        State,
+    },
+    http::{
+        header::{AUTHORIZATION, SEC_WEBSOCKET_PROTOCOL},
+        HeaderMap, HeaderValue,
+    },
+    response::Response,
+};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{sync::Arc, time::Duration};
+use tokio::time::timeout;
+use tokio_tungstenite::tungstenite::{self, protocol::frame::coding::CloseCode};
+use tracing::info;
+
+/// Префикс subprotocol, которым браузерные клиенты передают API ключ
+/// (браузерный WebSocket API не позволяет задать заголовок Authorization).
+const API_KEY_PROTOCOL_PREFIX: &str = "openai-insecure-api-key.";
+
+/// Время ожидания ответного Close от второй стороны после закрытия первой.
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Тело запроса `POST /v1/realtime/client_secrets`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateClientSecretParams {
+    /// Время жизни ключа (`anchor`, `seconds`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_after: Option<Value>,
+    /// Конфигурация сессии (`realtime` или `transcription`), привязанная к ключу
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<Value>,
+}
+
+/// Проксирует WebSocket соединение Realtime API.
+///
+/// Соединение с OpenAI открывается до завершения upgrade клиента, поэтому ошибки
+/// handshake (неверный ключ, неизвестная модель) возвращаются клиенту обычным
+/// HTTP ответом OpenAI. Query string (`model`, `call_id`) и subprotocols клиента
+/// передаются в OpenAI без изменений, заголовки handshake OpenAI (`x-request-id`,
+/// `x-ratelimit-*`) - клиенту в ответе `101 Switching Protocols`.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок или subprotocol `openai-insecure-api-key.<key>`
+/// * `query` - Параметры query string (`model`, `call_id`)
+/// * `upgrade` - WebSocket upgrade запроса клиента
+///
+/// # Returns
+/// * `Ok(Response)` - `101 Switching Protocols`, после которого начинается ретрансляция
+/// * `Err(AppError)` - Ошибка авторизации, соединения или отказ OpenAI в handshake
+///
+/// # Пример
+/// ```bash
+/// websocat -H "Authorization: Bearer sk-..." \
+///   "ws://localhost:8080/v1/realtime?model=gpt-realtime"
+/// ```
+pub async fn realtime_websocket(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+    WebSocketUpgrade(upgrade): WebSocketUpgrade,
+) -> Result<Response, AppError> {
+    info!("🎙️ Realtime WebSocket request");
+
+    let headers = with_protocol_api_key(headers);
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let (upstream, mut upstream_headers) = client
+        .connect_websocket("/realtime", &query, headers.get(SEC_WEBSOCKET_PROTOCOL))
+        .await?;
+
+    info!("✅ Realtime сессия открыта");
+
+    let protocol = upstream_headers
+        .remove(SEC_WEBSOCKET_PROTOCOL)
+        .and_then(|v| v.to_str().ok().map(str::to_string));
+    let upgrade = match protocol {
+        Some(protocol) => upgrade.protocols([protocol]),
+        None => upgrade,
+    };
+    let mut response = upgrade.on_upgrade(move |socket| relay(socket, upstream));
+    response.headers_mut().extend(upstream_headers);
+    Ok(response)
+}
+
+/// Создает временный ключ (client secret) для подключения к Realtime API.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `CreateClientSecretParams` с `expires_after` и `session`
+///
+/// # Returns
+/// * `Ok(JsonReply<Value>)` - Временный ключ (`value`, `expires_at`) и конфигурация сессии
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+///
+/// # Пример
+/// ```bash
+/// curl -X POST http://localhost:8080/v1/realtime/client_secrets \
+///   -H "Content-Type: application/json" \
+///   -H "Authorization: Bearer sk-..." \
+///   -d '{"expires_after":{"anchor":"created_at","seconds":600},"session":{"type":"realtime","model":"gpt-realtime"}}'
+/// ```
+pub async fn create_client_secret(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    request: JsonBody<CreateClientSecretParams>,
+) -> Result<JsonReply<Value>, AppError> {
+    info!("🎙️ Create realtime client secret request");
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<Value> = client.post("/realtime/client_secrets", &request).await?;
+
+    info!("✅ Realtime client secret создан");
+    Ok(response)
+}
+
+/// Подставляет ключ из subprotocol `openai-insecure-api-key.<key>` в Authorization,
+/// если клиент не передал заголовок Authorization.
+fn with_protocol_api_key(mut headers: HeaderMap) -> HeaderMap {
+    if headers.contains_key(AUTHORIZATION) {
+        return headers;
+    }
+
+    let api_key = headers
+        .get(SEC_WEBSOCKET_PROTOCOL)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|protocols| {
+            protocols
+                .split(',')
+                .find_map(|protocol| protocol.trim().strip_prefix(API_KEY_PROTOCOL_PREFIX))
+        })
+        .and_then(|key| HeaderValue::from_str(&format!("Bearer {}", key)).ok());
+
+    if let Some(api_key) = api_key {
+        headers.insert(AUTHORIZATION, api_key);
+    }
+    headers
+}
+
+/// Ретранслирует сообщения между клиентом и OpenAI до закрытия соединения.
+///
+/// Каждое направление отправляет следующее сообщение только после того, как
+/// предыдущее записано в сокет получателя, поэтому медленная сторона замедляет чтение
+/// с другой стороны (backpressure через TCP), а прокси не накапливает сообщения в памяти.
+/// Ping/pong каждая сторона обрабатывает сама.
+///
+/// Как только одно направление завершается (Close, разрыв соединения или ошибка записи),
+/// второе прекращается, другой стороне отправляется Close и ее ответный Close ожидается
+/// не дольше [`CLOSE_TIMEOUT`], после чего оба соединения закрываются.
+async fn relay(client: WebSocket, upstream: UpstreamWebSocket) {
+    let (mut client_tx, mut client_rx) = client.split();
+    let (mut upstream_tx, mut upstream_rx) = upstream.split();
+
+    let client_to_upstream = async {
+        while let Some(Ok(message)) = client_rx.next().await {
+            let Some(message) = into_upstream_message(message) else {
+                continue;
+            };
+            let close = matches!(message, tungstenite::Message::Close(_));
+            if upstream_tx.send(message).await.is_err() || close {
+                break;
+            }
+        }
+    };
+
+    let upstream_to_client = async {
+        while let Some(Ok(message)) = upstream_rx.next().await {
+            let Some(message) = into_client_message(message) else {
+                continue;
+            };
+            let close = matches!(message, Message::Close(_));
+            if client_tx.send(message).await.is_err() || close {
+                break;
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = client_to_upstream => {}
+        _ = upstream_to_client => {}
+    }
+
+    // Отправляем Close обеим сторонам (та, что закрылась первой, получает ответ на свой
+    // Close) и ждем ответный Close ограниченное время
+    let _ = timeout(CLOSE_TIMEOUT, async {
+        let _ = tokio::join!(client_tx.close(), upstream_tx.close());
+        tokio::join!(
+            async { while let Some(Ok(_)) = client_rx.next().await {} },
+            async { while let Some(Ok(_)) = upstream_rx.next().await {} },
+        );
+    })
+    .await;
+
+    info!("🔌 Realtime сессия закрыта");
+}
+
+/// Преобразует сообщение клиента в сообщение для OpenAI (ping/pong не пересылаются).
+fn into_upstream_message(message: Message) -> Option<tungstenite::Message> {
+    match message {
+        Message::Text(text) => Some(tungstenite::Message::text(text.as_str())),
+        Message::Binary(data) => Some(tungstenite::Message::Binary(data)),
+        Message::Close(frame) => Some(tungstenite::Message::Close(frame.map(|frame| {
+            tungstenite::protocol::CloseFrame {
+                code: CloseCode::from(frame.code),
+                reason: frame.reason.as_str().into(),
+            }
+        }))),
+        Message::Ping(_) | Message::Pong(_) => None,
+    }
+}
+
+/// Преобразует сообщение OpenAI в сообщение для клиента (ping/pong не пересылаются).
+fn into_client_message(message: tungstenite::Message) -> Option<Message> {
+    match message {
+        tungstenite::Message::Text(text) => Some(Message::Text(Utf8Bytes::from(text.as_str()))),
+        tungstenite::Message::Binary(data) => Some(Message::Binary(data)),
+        tungstenite::Message::Close(frame) => Some(Message::Close(frame.map(|frame| CloseFrame {
+            code: frame.code.into(),
+            reason: frame.reason.as_str().into(),
+        }))),
+        tungstenite::Message::Ping(_)
+        | tungstenite::Message::Pong(_)
+        | tungstenite::Message::Frame(_) => None,
+    }
+}
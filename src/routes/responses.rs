@@ -1,141 +1,273 @@
 //! Обработчики Responses API.
 //!
 //! Управление объектами responses: создание, получение, удаление и отмена.
+//! Поддерживает потоковый режим (SSE) при создании и возобновление потока фоновых responses.
+//! Вспомогательные эндпоинты: входные элементы response, подсчет входных токенов и
+//! сжатие (compaction) разговора.
 
-use crate::{error::AppError, state::AppState, utils::create_client_from_headers};
-use async_openai::types::responses::{CreateResponse, DeleteResponse, Response};
+use crate::{
+    error::AppError,
+    moderation,
+    payload::{JsonBody, JsonReply},
+    query::Query,
+    routes::ListQuery,
+    state::AppState,
+    utils::create_client_from_headers,
+};
+use async_openai::types::responses::{
+    CompactResource, CompactResponseRequest, CreateResponse, DeleteResponse, Response,
+    ResponseItemList, TokenCountsBody, TokenCountsResource,
+};
 use axum::{
     extract::{Path, State},
     http::HeaderMap,
-    Json,
+    response::{IntoResponse, Response as HttpResponse},
 };
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::info;
 
 /// Создает response через OpenAI Responses API.
 ///
+/// При `"stream": true` события (`response.output_text.delta`, вызовы инструментов,
+/// reasoning и т.д.) ретранслируются клиенту через SSE без изменений, поэтому
+/// проходят и типы событий, неизвестные закрепленной версии `async-openai`.
+///
+/// Если включена политика модерации, пользовательский `input` предварительно проверяется
+/// через `/v1/moderations` (см. [`moderation::preflight`]).
+///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `headers` - Authorization заголовок клиента
 /// * `request` - `CreateResponse` с параметрами ответа
 ///
 /// # Returns
-/// * `Ok(Json<Response>)` - Созданный response
+/// * `Ok(HttpResponse)` - Созданный response (JSON) или SSE-поток событий
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn create_response(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-    Json(request): Json<CreateResponse>,
-) -> Result<Json<Response>, AppError> {
-    info!("💬 Create response request");
-
-    let client = create_client_from_headers(&headers, false)?;
-
-    let response = client
-        .responses()
-        .create(request)
-        .await
-        .map_err(|e| {
-            error!("❌ Create response error: {}", e);
-            AppError(format!("Create response error: {}", e))
-        })?;
-
-    info!("✅ Response создан: {}", response.id);
-    Ok(Json(response))
+    request: JsonBody<CreateResponse>,
+) -> Result<HttpResponse, AppError> {
+    info!("💬 Create response request: model={}", request.model());
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    // Предварительная модерация ввода (если включена в конфигурации)
+    let flagged = moderation::preflight(&state.config.moderation, &client, &request).await?;
+
+    if request.stream() {
+        let mut response = client.post_stream("/responses", &request).await?;
+        moderation::mark(&mut response, &flagged);
+
+        info!("✅ Response stream открыт");
+        return Ok(response);
+    }
+
+    let response: JsonReply<Response> = client.post("/responses", &request).await?;
+
+    info!(
+        "✅ Response создан: {} (total_tokens={})",
+        response.id(),
+        response.total_tokens().unwrap_or_default()
+    );
+    let mut response = response.into_response();
+    moderation::mark(&mut response, &flagged);
+    Ok(response)
 }
 
 /// Возвращает response по идентификатору.
 ///
+/// С `?stream=true` возобновляет SSE-поток фонового response; `starting_after`
+/// задает номер события, после которого продолжить. Query string (включая
+/// повторяющийся `include[]` и `include_obfuscation`) передается в OpenAI в исходном
+/// виде в обоих режимах.
+///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `response_id` - Идентификатор response
+/// * `query` - Параметры `stream`, `starting_after`, `include[]`, `include_obfuscation`
 /// * `headers` - Authorization заголовок клиента
 ///
 /// # Returns
-/// * `Ok(Json<Response>)` - Найденный response
+/// * `Ok(HttpResponse)` - Найденный response (JSON) или SSE-поток событий
 /// * `Err(AppError)` - Ошибка запроса или объект не найден
+///
+/// # Пример
+/// ```bash
+/// curl -N "http://localhost:8080/v1/responses/resp_123?stream=true&starting_after=42" \
+///   -H "Authorization: Bearer sk-..."
+/// ```
 pub async fn get_response(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path(response_id): Path<String>,
+    Query(query): Query<ListQuery>,
     headers: HeaderMap,
-) -> Result<Json<Response>, AppError> {
+) -> Result<HttpResponse, AppError> {
     info!("💬 Get response request: {}", response_id);
 
-    let client = create_client_from_headers(&headers, false)?;
+    let client = create_client_from_headers(&state, &headers, false)?;
+    let path = format!("/responses/{}", response_id);
+
+    let stream = query
+        .iter()
+        .any(|(name, value)| name == "stream" && value == "true");
+    if stream {
+        let response = client.get_stream(&path, &query).await?;
+
+        info!("✅ Response stream возобновлен: {}", response_id);
+        return Ok(response);
+    }
 
-    let response = client
-        .responses()
-        .retrieve(&response_id)
-        .await
-        .map_err(|e| {
-            error!("❌ Get response error: {}", e);
-            AppError(format!("Get response error: {}", e))
-        })?;
+    let response: JsonReply<Response> = client.get_with_query(&path, &query).await?;
 
     info!("✅ Response получен: {}", response_id);
-    Ok(Json(response))
+    Ok(response.into_response())
 }
 
 /// Удаляет response по идентификатору.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `response_id` - Идентификатор response
 /// * `headers` - Authorization заголовок клиента
 ///
 /// # Returns
-/// * `Ok(Json<DeleteResponse>)` - Подтверждение удаления
+/// * `Ok(JsonReply<DeleteResponse>)` - Подтверждение удаления
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn delete_response(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path(response_id): Path<String>,
     headers: HeaderMap,
-) -> Result<Json<DeleteResponse>, AppError> {
+) -> Result<JsonReply<DeleteResponse>, AppError> {
     info!("💬 Delete response request: {}", response_id);
 
-    let client = create_client_from_headers(&headers, false)?;
+    let client = create_client_from_headers(&state, &headers, false)?;
 
-    let response = client
-        .responses()
-        .delete(&response_id)
-        .await
-        .map_err(|e| {
-            error!("❌ Delete response error: {}", e);
-            AppError(format!("Delete response error: {}", e))
-        })?;
+    let response: JsonReply<DeleteResponse> = client
+        .delete(&format!("/responses/{}", response_id))
+        .await?;
 
     info!("✅ Response удалён: {}", response_id);
-    Ok(Json(response))
+    Ok(response)
 }
 
 /// Отменяет выполнение response по идентификатору.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `response_id` - Идентификатор response
 /// * `headers` - Authorization заголовок клиента
 ///
 /// # Returns
-/// * `Ok(Json<Response>)` - Отмененный response
+/// * `Ok(JsonReply<Response>)` - Отмененный response
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn cancel_response(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path(response_id): Path<String>,
     headers: HeaderMap,
-) -> Result<Json<Response>, AppError> {
+) -> Result<JsonReply<Response>, AppError> {
     info!("💬 Cancel response request: {}", response_id);
 
-    let client = create_client_from_headers(&headers, false)?;
+    let client = create_client_from_headers(&state, &headers, false)?;
 
-    let response = client
-        .responses()
-        .cancel(&response_id)
-        .await
-        .map_err(|e| {
-            error!("❌ Cancel response error: {}", e);
-            AppError(format!("Cancel response error: {}", e))
-        })?;
+    let response: JsonReply<Response> = client
+        .post_empty(&format!("/responses/{}/cancel", response_id))
+        .await?;
 
     info!("✅ Response отменён: {}", response_id);
-    Ok(Json(response))
+    Ok(response)
+}
+
+/// Возвращает входные элементы response (сообщения, вызовы инструментов и т.д.).
+///
+/// Параметры query string (`limit`, `order`, `after` и повторяющийся `include[]`)
+/// передаются в OpenAI в исходном виде.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `response_id` - Идентификатор response
+/// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации и `include[]`
+///
+/// # Returns
+/// * `Ok(JsonReply<ResponseItemList>)` - Страница входных элементов (с пагинацией)
+/// * `Err(AppError)` - Ошибка запроса или response не найден
+pub async fn list_input_items(
+    State(state): State<Arc<AppState>>,
+    Path(response_id): Path<String>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ResponseItemList>, AppError> {
+    info!("💬 List input items request: {}", response_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<ResponseItemList> = client
+        .get_with_query(&format!("/responses/{}/input_items", response_id), &query)
+        .await?;
+
+    info!("✅ Input items list получен: {}", response_id);
+    Ok(response)
+}
+
+/// Подсчитывает входные токены запроса к Responses API без генерации ответа.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `TokenCountsBody` с теми же `model`, `input`, `tools` и т.д., что и у `POST /v1/responses`
+///
+/// # Returns
+/// * `Ok(JsonReply<TokenCountsResource>)` - Количество входных токенов
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+///
+/// # Пример
+/// ```bash
+/// curl -X POST http://localhost:8080/v1/responses/input_tokens \
+///   -H "Content-Type: application/json" \
+///   -H "Authorization: Bearer sk-..." \
+///   -d '{"model":"gpt-4.1","input":"Tell me a joke."}'
+/// ```
+pub async fn count_input_tokens(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    request: JsonBody<TokenCountsBody>,
+) -> Result<JsonReply<TokenCountsResource>, AppError> {
+    info!("💬 Input tokens request: model={}", request.model());
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<TokenCountsResource> =
+        client.post("/responses/input_tokens", &request).await?;
+
+    info!("✅ Input tokens подсчитаны");
+    Ok(response)
+}
+
+/// Сжимает разговор (compaction) для продолжения в пределах контекстного окна.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `CompactResponseRequest` с моделью и `input` или `previous_response_id`
+///
+/// # Returns
+/// * `Ok(JsonReply<CompactResource>)` - Сжатые элементы разговора и usage
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn compact_response(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    request: JsonBody<CompactResponseRequest>,
+) -> Result<JsonReply<CompactResource>, AppError> {
+    info!("💬 Compact request: model={}", request.model());
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<CompactResource> = client.post("/responses/compact", &request).await?;
+
+    info!(
+        "✅ Compaction выполнен: total_tokens={}",
+        response.total_tokens().unwrap_or_default()
+    );
+    Ok(response)
 }
@@ -1,260 +1,346 @@
 //! Обработчики Runs API (Assistants v2).
 //!
 //! Управление выполнениями (runs) ассистентов в рамках thread: создание, получение,
-//! отмена, обновление и отправка результатов инструментов.
+//! отмена, обновление и отправка результатов инструментов. Создание run и отправка
+//! tool outputs поддерживают потоковый режим (SSE) с событиями Assistants v2.
+//! Шаги run (run steps) позволяют просмотреть вызовы инструментов, включая результаты
+//! `file_search`.
 
-use crate::{error::AppError, state::AppState, utils::create_client_from_headers};
+use crate::{
+    error::AppError,
+    payload::{JsonBody, JsonReply},
+    query::Query,
+    routes::ListQuery,
+    state::AppState,
+    utils::create_client_from_headers,
+};
 use async_openai::types::assistants::{
-    CreateRunRequest, CreateThreadAndRunRequest, ListRunsResponse, ModifyRunRequest, RunObject,
-    SubmitToolOutputsRunRequest,
+    CreateRunRequest, CreateThreadAndRunRequest, ListRunStepsResponse, ListRunsResponse,
+    ModifyRunRequest, RunObject, RunStepObject, SubmitToolOutputsRunRequest,
 };
 use axum::{
     extract::{Path, State},
     http::HeaderMap,
-    Json,
+    response::{IntoResponse, Response},
 };
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::info;
 
 /// Создает run в указанном thread.
 ///
+/// При `"stream": true` события run (`thread.run.*`, `thread.run.step.*`,
+/// `thread.message.delta` и т.д.) ретранслируются клиенту через SSE.
+///
+/// Query string (например, `include[]=step_details.tool_calls[*].file_search.results[*].content`)
+/// передается в OpenAI без изменений.
+///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `thread_id` - Идентификатор thread
+/// * `query` - Параметры query string (например, `include[]`)
 /// * `headers` - Authorization заголовок клиента
 /// * `request` - `CreateRunRequest` с инструкциями/параметрами запуска
 ///
 /// # Returns
-/// * `Ok(Json<RunObject>)` - Созданный run
+/// * `Ok(Response)` - Созданный run (JSON) или SSE-поток событий
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn create_run(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path(thread_id): Path<String>,
+    Query(query): Query<ListQuery>,
     headers: HeaderMap,
-    Json(request): Json<CreateRunRequest>,
-) -> Result<Json<RunObject>, AppError> {
+    request: JsonBody<CreateRunRequest>,
+) -> Result<Response, AppError> {
     info!("🏃 Create run request in thread: {}", thread_id);
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let path = format!("/threads/{}/runs", thread_id);
+
+    if request.stream() {
+        let response = client
+            .post_stream_with_query(&path, &query, &request)
+            .await?;
+
+        info!("✅ Run stream открыт в thread: {}", thread_id);
+        return Ok(response);
+    }
 
-    let response = client
-        .threads()
-        .runs(&thread_id)
-        .create(request)
-        .await
-        .map_err(|e| {
-            error!("❌ Create run error: {}", e);
-            AppError(format!("Create run error: {}", e))
-        })?;
+    let response: JsonReply<RunObject> = client.post_with_query(&path, &query, &request).await?;
 
-    info!("✅ Run создан: {}", response.id);
-    Ok(Json(response))
+    info!("✅ Run создан: {}", response.id());
+    Ok(response.into_response())
 }
 
 /// Возвращает список runs в thread.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `thread_id` - Идентификатор thread
 /// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации (`limit`, `order`, `after`, `before`)
 ///
 /// # Returns
-/// * `Ok(Json<ListRunsResponse>)` - Список runs (с пагинацией)
+/// * `Ok(JsonReply<ListRunsResponse>)` - Список runs (с пагинацией)
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn list_runs(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path(thread_id): Path<String>,
     headers: HeaderMap,
-) -> Result<Json<ListRunsResponse>, AppError> {
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ListRunsResponse>, AppError> {
     info!("🏃 List runs request in thread: {}", thread_id);
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .threads()
-        .runs(&thread_id)
-        .list()
-        .await
-        .map_err(|e| {
-            error!("❌ List runs error: {}", e);
-            AppError(format!("List runs error: {}", e))
-        })?;
+    let response: JsonReply<ListRunsResponse> = client
+        .get_with_query(&format!("/threads/{}/runs", thread_id), &query)
+        .await?;
 
     info!("✅ Runs list получен");
-    Ok(Json(response))
+    Ok(response)
 }
 
 /// Возвращает run по идентификатору в рамках thread.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `thread_id` - Идентификатор thread
 /// * `run_id` - Идентификатор run
 /// * `headers` - Authorization заголовок клиента
 ///
 /// # Returns
-/// * `Ok(Json<RunObject>)` - Найденный run
+/// * `Ok(JsonReply<RunObject>)` - Найденный run
 /// * `Err(AppError)` - Ошибка запроса или run не найден
 pub async fn get_run(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path((thread_id, run_id)): Path<(String, String)>,
     headers: HeaderMap,
-) -> Result<Json<RunObject>, AppError> {
+) -> Result<JsonReply<RunObject>, AppError> {
     info!("🏃 Get run request: {} in thread: {}", run_id, thread_id);
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .threads()
-        .runs(&thread_id)
-        .retrieve(&run_id)
-        .await
-        .map_err(|e| {
-            error!("❌ Get run error: {}", e);
-            AppError(format!("Get run error: {}", e))
-        })?;
+    let response: JsonReply<RunObject> = client
+        .get(&format!("/threads/{}/runs/{}", thread_id, run_id))
+        .await?;
 
     info!("✅ Run получен");
-    Ok(Json(response))
+    Ok(response)
+}
+
+/// Возвращает список шагов run (создание сообщений и вызовы инструментов).
+///
+/// Параметры query string (`limit`, `order`, `after`, `before` и повторяющийся
+/// `include[]`, например `include[]=step_details.tool_calls[*].file_search.results[*].content`)
+/// передаются в OpenAI в исходном виде.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `thread_id` - Идентификатор thread
+/// * `run_id` - Идентификатор run
+/// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации и `include[]`
+///
+/// # Returns
+/// * `Ok(JsonReply<ListRunStepsResponse>)` - Список шагов run (с пагинацией)
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn list_run_steps(
+    State(state): State<Arc<AppState>>,
+    Path((thread_id, run_id)): Path<(String, String)>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ListRunStepsResponse>, AppError> {
+    info!(
+        "🏃 List run steps request: {} in thread: {}",
+        run_id, thread_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<ListRunStepsResponse> = client
+        .get_with_query(
+            &format!("/threads/{}/runs/{}/steps", thread_id, run_id),
+            &query,
+        )
+        .await?;
+
+    info!("✅ Run steps list получен");
+    Ok(response)
+}
+
+/// Возвращает шаг run по идентификатору.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `thread_id` - Идентификатор thread
+/// * `run_id` - Идентификатор run
+/// * `step_id` - Идентификатор шага
+/// * `headers` - Authorization заголовок клиента
+/// * `query` - Повторяющийся параметр `include[]`
+///
+/// # Returns
+/// * `Ok(JsonReply<RunStepObject>)` - Найденный шаг run
+/// * `Err(AppError)` - Ошибка запроса или шаг не найден
+pub async fn get_run_step(
+    State(state): State<Arc<AppState>>,
+    Path((thread_id, run_id, step_id)): Path<(String, String, String)>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<RunStepObject>, AppError> {
+    info!(
+        "🏃 Get run step request: {} in run: {}, thread: {}",
+        step_id, run_id, thread_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<RunStepObject> = client
+        .get_with_query(
+            &format!("/threads/{}/runs/{}/steps/{}", thread_id, run_id, step_id),
+            &query,
+        )
+        .await?;
+
+    info!("✅ Run step получен");
+    Ok(response)
 }
 
 /// Обновляет run по идентификатору.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `thread_id` - Идентификатор thread
 /// * `run_id` - Идентификатор run
 /// * `headers` - Authorization заголовок клиента
 /// * `request` - `ModifyRunRequest` с изменениями
 ///
 /// # Returns
-/// * `Ok(Json<RunObject>)` - Обновленный run
+/// * `Ok(JsonReply<RunObject>)` - Обновленный run
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn modify_run(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path((thread_id, run_id)): Path<(String, String)>,
     headers: HeaderMap,
-    Json(request): Json<ModifyRunRequest>,
-) -> Result<Json<RunObject>, AppError> {
+    request: JsonBody<ModifyRunRequest>,
+) -> Result<JsonReply<RunObject>, AppError> {
     info!("🏃 Modify run request: {} in thread: {}", run_id, thread_id);
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .threads()
-        .runs(&thread_id)
-        .update(&run_id, request)
-        .await
-        .map_err(|e| {
-            error!("❌ Modify run error: {}", e);
-            AppError(format!("Modify run error: {}", e))
-        })?;
+    let response: JsonReply<RunObject> = client
+        .post(&format!("/threads/{}/runs/{}", thread_id, run_id), &request)
+        .await?;
 
     info!("✅ Run обновлен");
-    Ok(Json(response))
+    Ok(response)
 }
 
 /// Отменяет run по идентификатору.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `thread_id` - Идентификатор thread
 /// * `run_id` - Идентификатор run
 /// * `headers` - Authorization заголовок клиента
 ///
 /// # Returns
-/// * `Ok(Json<RunObject>)` - Отмененный run
+/// * `Ok(JsonReply<RunObject>)` - Отмененный run
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn cancel_run(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path((thread_id, run_id)): Path<(String, String)>,
     headers: HeaderMap,
-) -> Result<Json<RunObject>, AppError> {
+) -> Result<JsonReply<RunObject>, AppError> {
     info!("🏃 Cancel run request: {} in thread: {}", run_id, thread_id);
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .threads()
-        .runs(&thread_id)
-        .cancel(&run_id)
-        .await
-        .map_err(|e| {
-            error!("❌ Cancel run error: {}", e);
-            AppError(format!("Cancel run error: {}", e))
-        })?;
+    let response: JsonReply<RunObject> = client
+        .post_empty(&format!("/threads/{}/runs/{}/cancel", thread_id, run_id))
+        .await?;
 
     info!("✅ Run отменен");
-    Ok(Json(response))
+    Ok(response)
 }
 
 /// Отправляет результаты работы инструментов (tool outputs) для run.
 ///
+/// При `"stream": true` продолжение run ретранслируется клиенту через SSE, что позволяет
+/// интерактивно вести tool-calling ассистента через прокси.
+///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `thread_id` - Идентификатор thread
 /// * `run_id` - Идентификатор run
 /// * `headers` - Authorization заголовок клиента
 /// * `request` - `SubmitToolOutputsRunRequest` с данными инструментов
 ///
 /// # Returns
-/// * `Ok(Json<RunObject>)` - Обновленный run после передачи результатов
+/// * `Ok(Response)` - Обновленный run после передачи результатов (JSON) или SSE-поток событий
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn submit_tool_outputs(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path((thread_id, run_id)): Path<(String, String)>,
     headers: HeaderMap,
-    Json(request): Json<SubmitToolOutputsRunRequest>,
-) -> Result<Json<RunObject>, AppError> {
+    request: JsonBody<SubmitToolOutputsRunRequest>,
+) -> Result<Response, AppError> {
     info!(
         "🏃 Submit tool outputs request: {} in thread: {}",
         run_id, thread_id
     );
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .threads()
-        .runs(&thread_id)
-        .submit_tool_outputs(&run_id, request)
-        .await
-        .map_err(|e| {
-            error!("❌ Submit tool outputs error: {}", e);
-            AppError(format!("Submit tool outputs error: {}", e))
-        })?;
+    if request.stream() {
+        let path = format!("/threads/{}/runs/{}/submit_tool_outputs", thread_id, run_id);
+        let response = client.post_stream(&path, &request).await?;
+
+        info!("✅ Tool outputs отправлены, stream открыт");
+        return Ok(response);
+    }
+
+    let response: JsonReply<RunObject> = client
+        .post(
+            &format!("/threads/{}/runs/{}/submit_tool_outputs", thread_id, run_id),
+            &request,
+        )
+        .await?;
 
     info!("✅ Tool outputs отправлены");
-    Ok(Json(response))
+    Ok(response.into_response())
 }
 
 /// Создает thread и сразу же run (удобно для single-call сценариев).
 ///
+/// При `"stream": true` события (начиная с `thread.created`) ретранслируются через SSE.
+///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `headers` - Authorization заголовок клиента
 /// * `request` - `CreateThreadAndRunRequest` с параметрами thread и run
 ///
 /// # Returns
-/// * `Ok(Json<RunObject>)` - Созданный run (и thread) с идентификатором
+/// * `Ok(Response)` - Созданный run (и thread) с идентификатором (JSON) или SSE-поток событий
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn create_thread_and_run(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-    Json(request): Json<CreateThreadAndRunRequest>,
-) -> Result<Json<RunObject>, AppError> {
+    request: JsonBody<CreateThreadAndRunRequest>,
+) -> Result<Response, AppError> {
     info!("🏃 Create thread and run request");
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    if request.stream() {
+        let response = client.post_stream("/threads/runs", &request).await?;
+
+        info!("✅ Thread and run stream открыт");
+        return Ok(response);
+    }
 
-    let response = client
-        .threads()
-        .create_and_run(request)
-        .await
-        .map_err(|e| {
-            error!("❌ Create thread and run error: {}", e);
-            AppError(format!("Create thread and run error: {}", e))
-        })?;
+    let response: JsonReply<RunObject> = client.post("/threads/runs", &request).await?;
 
-    info!("✅ Thread and run созданы: {}", response.id);
-    Ok(Json(response))
+    info!("✅ Thread and run созданы: {}", response.id());
+    Ok(response.into_response())
 }
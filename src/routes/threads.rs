@@ -2,144 +2,123 @@
 //!
 //! Создание, получение, изменение и удаление потоков (threads) ассистентов.
 
-use crate::{error::AppError, state::AppState, utils::create_client_from_headers};
+use crate::{
+    error::AppError,
+    payload::{JsonBody, JsonReply},
+    state::AppState,
+    utils::create_client_from_headers,
+};
 use async_openai::types::assistants::{
     CreateThreadRequest, DeleteThreadResponse, ModifyThreadRequest, ThreadObject,
 };
 use axum::{
     extract::{Path, State},
     http::HeaderMap,
-    Json,
 };
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::info;
 
 /// Создает новый thread для Assistants API v2.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `headers` - Authorization заголовок клиента
 /// * `request` - `CreateThreadRequest` с начальными сообщениями/параметрами
 ///
 /// # Returns
-/// * `Ok(Json<ThreadObject>)` - Созданный thread с его `id`
+/// * `Ok(JsonReply<ThreadObject>)` - Созданный thread с его `id`
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn create_thread(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-    Json(request): Json<CreateThreadRequest>,
-) -> Result<Json<ThreadObject>, AppError> {
+    request: JsonBody<CreateThreadRequest>,
+) -> Result<JsonReply<ThreadObject>, AppError> {
     info!("💬 Create thread request");
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .threads()
-        .create(request)
-        .await
-        .map_err(|e| {
-            error!("❌ Create thread error: {}", e);
-            AppError(format!("Create thread error: {}", e))
-        })?;
+    let response: JsonReply<ThreadObject> = client.post("/threads", &request).await?;
 
-    info!("✅ Thread создан: {}", response.id);
-    Ok(Json(response))
+    info!("✅ Thread создан: {}", response.id());
+    Ok(response)
 }
 
 /// Возвращает thread по идентификатору.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `thread_id` - Идентификатор thread
 /// * `headers` - Authorization заголовок клиента
 ///
 /// # Returns
-/// * `Ok(Json<ThreadObject>)` - Найденный thread
+/// * `Ok(JsonReply<ThreadObject>)` - Найденный thread
 /// * `Err(AppError)` - Ошибка запроса или thread не найден
 pub async fn get_thread(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path(thread_id): Path<String>,
     headers: HeaderMap,
-) -> Result<Json<ThreadObject>, AppError> {
+) -> Result<JsonReply<ThreadObject>, AppError> {
     info!("💬 Get thread request: {}", thread_id);
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .threads()
-        .retrieve(&thread_id)
-        .await
-        .map_err(|e| {
-            error!("❌ Get thread error: {}", e);
-            AppError(format!("Get thread error: {}", e))
-        })?;
+    let response: JsonReply<ThreadObject> = client.get(&format!("/threads/{}", thread_id)).await?;
 
     info!("✅ Thread получен");
-    Ok(Json(response))
+    Ok(response)
 }
 
 /// Обновляет thread по идентификатору.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `thread_id` - Идентификатор thread
 /// * `headers` - Authorization заголовок клиента
 /// * `request` - `ModifyThreadRequest` с полями для обновления
 ///
 /// # Returns
-/// * `Ok(Json<ThreadObject>)` - Обновленный thread
+/// * `Ok(JsonReply<ThreadObject>)` - Обновленный thread
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn modify_thread(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path(thread_id): Path<String>,
     headers: HeaderMap,
-    Json(request): Json<ModifyThreadRequest>,
-) -> Result<Json<ThreadObject>, AppError> {
+    request: JsonBody<ModifyThreadRequest>,
+) -> Result<JsonReply<ThreadObject>, AppError> {
     info!("💬 Modify thread request: {}", thread_id);
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .threads()
-        .update(&thread_id, request)
-        .await
-        .map_err(|e| {
-            error!("❌ Modify thread error: {}", e);
-            AppError(format!("Modify thread error: {}", e))
-        })?;
+    let response: JsonReply<ThreadObject> = client
+        .post(&format!("/threads/{}", thread_id), &request)
+        .await?;
 
     info!("✅ Thread обновлен");
-    Ok(Json(response))
+    Ok(response)
 }
 
 /// Удаляет thread по идентификатору.
 ///
 /// # Arguments
-/// * `_state` - Состояние приложения
+/// * `state` - Состояние приложения с настройками upstream
 /// * `thread_id` - Идентификатор thread
 /// * `headers` - Authorization заголовок клиента
 ///
 /// # Returns
-/// * `Ok(Json<DeleteThreadResponse>)` - Подтверждение удаления
+/// * `Ok(JsonReply<DeleteThreadResponse>)` - Подтверждение удаления
 /// * `Err(AppError)` - Ошибка запроса или авторизации
 pub async fn delete_thread(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Path(thread_id): Path<String>,
     headers: HeaderMap,
-) -> Result<Json<DeleteThreadResponse>, AppError> {
+) -> Result<JsonReply<DeleteThreadResponse>, AppError> {
     info!("💬 Delete thread request: {}", thread_id);
 
-    let client = create_client_from_headers(&headers, true)?;
+    let client = create_client_from_headers(&state, &headers, true)?;
 
-    let response = client
-        .threads()
-        .delete(&thread_id)
-        .await
-        .map_err(|e| {
-            error!("❌ Delete thread error: {}", e);
-            AppError(format!("Delete thread error: {}", e))
-        })?;
+    let response: JsonReply<DeleteThreadResponse> =
+        client.delete(&format!("/threads/{}", thread_id)).await?;
 
     info!("✅ Thread удален");
-    Ok(Json(response))
+    Ok(response)
 }
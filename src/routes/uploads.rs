@@ -0,0 +1,444 @@
+//! Обработчики Uploads API.
+//!
+//! Загрузка больших файлов частями (до 8 ГБ): создание Upload, добавление частей,
+//! завершение и отмена. Дополнительно прокси предоставляет эндпоинт
+//! `POST /v1/uploads/stream`, который принимает файл одним потоковым телом и сам
+//! выполняет протокол Upload (create → parts → complete) в OpenAI.
+
+use crate::{
+    error::AppError,
+    multipart::MultipartForm,
+    payload::{JsonBody, JsonReply},
+    query::Query,
+    state::AppState,
+    upstream::UpstreamClient,
+    utils::create_client_from_headers,
+};
+use async_openai::types::uploads::{CreateUploadRequest, Upload, UploadPart};
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{
+        header::{CONTENT_LENGTH, CONTENT_TYPE},
+        HeaderMap,
+    },
+};
+use futures_util::StreamExt;
+use reqwest::multipart::{Form, Part};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Размер части, на которые `POST /v1/uploads/stream` режет тело запроса (16 МБ).
+const STREAM_PART_SIZE: usize = 16 * 1024 * 1024;
+
+/// Тело запроса `POST /v1/uploads/{upload_id}/complete`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CompleteUploadParams {
+    /// Идентификаторы частей в порядке следования в файле
+    pub part_ids: Vec<String>,
+    /// MD5 содержимого файла для проверки целостности
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub md5: Option<String>,
+}
+
+/// Параметры query string для `POST /v1/uploads/stream`.
+#[derive(Debug, Deserialize)]
+pub struct StreamUploadQuery {
+    /// Имя файла
+    pub filename: String,
+    /// Назначение файла (`assistants`, `batch`, `fine-tune`, `vision`...)
+    pub purpose: String,
+    /// MIME тип файла (по умолчанию Content-Type запроса)
+    pub mime_type: Option<String>,
+    /// Размер файла в байтах (по умолчанию Content-Length запроса)
+    pub bytes: Option<u64>,
+}
+
+/// Создает Upload, в который затем добавляются части файла.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `CreateUploadRequest` с именем, назначением, размером и MIME типом файла
+///
+/// # Returns
+/// * `Ok(JsonReply<Upload>)` - Созданный Upload в статусе `pending`
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+///
+/// # Пример
+/// ```bash
+/// curl -X POST http://localhost:8080/v1/uploads \
+///   -H "Content-Type: application/json" \
+///   -H "Authorization: Bearer sk-..." \
+///   -d '{"filename":"train.jsonl","purpose":"fine-tune","bytes":2147483648,"mime_type":"text/jsonl"}'
+/// ```
+pub async fn create_upload(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    request: JsonBody<CreateUploadRequest>,
+) -> Result<JsonReply<Upload>, AppError> {
+    info!("📤 Create upload request");
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<Upload> = client.post("/uploads", &request).await?;
+
+    info!("✅ Upload создан: {}", response.id());
+    Ok(response)
+}
+
+/// Добавляет часть файла (до 64 МБ) в Upload.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `upload_id` - Идентификатор Upload
+/// * `headers` - Authorization заголовок клиента
+/// * `form` - Поле multipart/form-data `data` с байтами части
+///
+/// # Returns
+/// * `Ok(JsonReply<UploadPart>)` - Добавленная часть с ее `id`
+/// * `Err(AppError)` - Ошибка чтения multipart или запроса к OpenAI
+pub async fn add_upload_part(
+    State(state): State<Arc<AppState>>,
+    Path(upload_id): Path<String>,
+    headers: HeaderMap,
+    form: MultipartForm,
+) -> Result<JsonReply<UploadPart>, AppError> {
+    if !form.has_file("data") {
+        return Err(AppError::MissingField("data"));
+    }
+
+    info!("📤 Add upload part request: {}", upload_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<UploadPart> = client
+        .post_multipart(&format!("/uploads/{}/parts", upload_id), form.into_form())
+        .await?;
+
+    info!("✅ Upload part добавлен: {}", response.id());
+    Ok(response)
+}
+
+/// Завершает Upload: OpenAI собирает части в файл, доступный в Files API.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `upload_id` - Идентификатор Upload
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `CompleteUploadParams` с упорядоченным списком `part_ids`
+///
+/// # Returns
+/// * `Ok(JsonReply<Upload>)` - Завершенный Upload с вложенным объектом `file`
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn complete_upload(
+    State(state): State<Arc<AppState>>,
+    Path(upload_id): Path<String>,
+    headers: HeaderMap,
+    request: JsonBody<CompleteUploadParams>,
+) -> Result<JsonReply<Upload>, AppError> {
+    info!("📤 Complete upload request: {}", upload_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<Upload> = client
+        .post(&format!("/uploads/{}/complete", upload_id), &request)
+        .await?;
+
+    info!("✅ Upload завершен");
+    Ok(response)
+}
+
+/// Отменяет Upload; добавленные части удаляются.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `upload_id` - Идентификатор Upload
+/// * `headers` - Authorization заголовок клиента
+///
+/// # Returns
+/// * `Ok(JsonReply<Upload>)` - Upload в статусе `cancelled`
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn cancel_upload(
+    State(state): State<Arc<AppState>>,
+    Path(upload_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<JsonReply<Upload>, AppError> {
+    info!("📤 Cancel upload request: {}", upload_id);
+
+    let client = create_client_from_headers(&state, &headers, false)?;
+
+    let response: JsonReply<Upload> = client
+        .post_empty(&format!("/uploads/{}/cancel", upload_id))
+        .await?;
+
+    info!("✅ Upload отменен");
+    Ok(response)
+}
+
+/// Загружает файл, переданный одним потоковым телом, через протокол Uploads API.
+///
+/// Создает Upload, режет тело по мере поступления на части по 16 МБ, отправляет их
+/// в `/uploads/{upload_id}/parts` и завершает Upload. В памяти одновременно хранится
+/// не больше одной части, лимит `server.body_limit_bytes` на этот эндпоинт не действует.
+/// Перед завершением размер полученного тела сверяется с заявленным. При любой ошибке
+/// после создания (чтение тела, несовпадение размера, части, завершение) Upload отменяется.
+///
+/// `Idempotency-Key` клиента в запросы к OpenAI не передается: он относится ко всему
+/// запросу клиента, а не к отдельным запросам протокола Upload.
+///
+/// Размер файла берется из `?bytes=` или Content-Length, MIME тип - из `?mime_type=`
+/// или Content-Type запроса.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization, Content-Length и Content-Type заголовки клиента
+/// * `query` - Имя, назначение и (опционально) размер и MIME тип файла
+/// * `body` - Содержимое файла
+///
+/// # Returns
+/// * `Ok(JsonReply<Upload>)` - Завершенный Upload с вложенным объектом `file`
+/// * `Err(AppError)` - Ошибка чтения тела, несовпадение размера или ошибка запроса к OpenAI
+///
+/// # Пример
+/// ```bash
+/// curl -X POST "http://localhost:8080/v1/uploads/stream?filename=train.jsonl&purpose=fine-tune" \
+///   -H "Authorization: Bearer sk-..." \
+///   -H "Content-Type: text/jsonl" \
+///   -T ./train.jsonl
+/// ```
+pub async fn stream_upload(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<StreamUploadQuery>,
+    body: Body,
+) -> Result<JsonReply<Upload>, AppError> {
+    let bytes = query
+        .bytes
+        .or_else(|| {
+            headers
+                .get(CONTENT_LENGTH)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok())
+        })
+        .ok_or(AppError::MissingField("bytes"))?;
+    let mime_type = query
+        .mime_type
+        .as_deref()
+        .or_else(|| headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok()))
+        .unwrap_or("application/octet-stream");
+
+    info!(
+        "📤 Stream upload request: filename={}, purpose={}, bytes={}",
+        query.filename, query.purpose, bytes
+    );
+
+    let client = create_client_from_headers(&state, &headers, false)?.without_idempotency_key();
+
+    let upload: JsonReply<Upload> = client
+        .post(
+            "/uploads",
+            &json!({
+                "filename": query.filename,
+                "purpose": query.purpose,
+                "bytes": bytes,
+                "mime_type": mime_type,
+            }),
+        )
+        .await?;
+    let upload_id = upload.id().to_string();
+
+    match complete_stream_upload(&client, &upload_id, bytes, body).await {
+        Ok(response) => {
+            info!("✅ Stream upload завершен: {}", upload_id);
+            Ok(response)
+        }
+        Err(e) => {
+            if let Err(cancel_error) = client
+                .post_empty::<Upload>(&format!("/uploads/{}/cancel", upload_id))
+                .await
+            {
+                warn!(
+                    "⚠️ Upload {} не удалось отменить: {}",
+                    upload_id, cancel_error
+                );
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Добавляет тело запроса в Upload частями и завершает Upload.
+///
+/// # Returns
+/// * `Ok(JsonReply<Upload>)` - Завершенный Upload
+/// * `Err(AppError)` - Ошибка чтения тела, несовпадение размера или ошибка запроса к OpenAI
+async fn complete_stream_upload(
+    client: &UpstreamClient,
+    upload_id: &str,
+    bytes: u64,
+    body: Body,
+) -> Result<JsonReply<Upload>, AppError> {
+    let part_ids = upload_parts(client, upload_id, bytes, body).await?;
+
+    client
+        .post(
+            &format!("/uploads/{}/complete", upload_id),
+            &CompleteUploadParams {
+                part_ids,
+                md5: None,
+            },
+        )
+        .await
+}
+
+/// Режет тело запроса на части по [`STREAM_PART_SIZE`] и добавляет их в Upload.
+///
+/// Прерывается, как только тело превышает заявленный размер `bytes`; тело короче
+/// заявленного отклоняется до отправки последней части.
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - Идентификаторы добавленных частей по порядку
+/// * `Err(AppError)` - Ошибка чтения тела, несовпадение размера или ошибка запроса к OpenAI
+async fn upload_parts(
+    client: &UpstreamClient,
+    upload_id: &str,
+    bytes: u64,
+    body: Body,
+) -> Result<Vec<String>, AppError> {
+    let mut stream = body.into_data_stream();
+    let mut buffer = Vec::with_capacity(STREAM_PART_SIZE);
+    let mut part_ids = Vec::new();
+    let mut received: u64 = 0;
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| AppError::BodyRead(e.to_string()))?;
+        received += chunk.len() as u64;
+        if received > bytes {
+            return Err(AppError::UploadSizeMismatch {
+                declared: bytes,
+                received,
+            });
+        }
+        buffer.extend_from_slice(&chunk);
+
+        while buffer.len() >= STREAM_PART_SIZE {
+            let rest = buffer.split_off(STREAM_PART_SIZE);
+            let part = std::mem::replace(&mut buffer, rest);
+            part_ids.push(add_part(client, upload_id, part).await?);
+        }
+    }
+    if received != bytes {
+        return Err(AppError::UploadSizeMismatch {
+            declared: bytes,
+            received,
+        });
+    }
+    if !buffer.is_empty() {
+        part_ids.push(add_part(client, upload_id, buffer).await?);
+    }
+
+    Ok(part_ids)
+}
+
+/// Отправляет одну часть файла в `/uploads/{upload_id}/parts`.
+///
+/// # Returns
+/// * `Ok(String)` - Идентификатор добавленной части
+/// * `Err(AppError)` - Ошибка запроса к OpenAI
+async fn add_part(
+    client: &UpstreamClient,
+    upload_id: &str,
+    data: Vec<u8>,
+) -> Result<String, AppError> {
+    let size = data.len();
+    let form = Form::new().part("data", Part::bytes(data).file_name("part"));
+
+    let part: JsonReply<UploadPart> = client
+        .post_multipart(&format!("/uploads/{}/parts", upload_id), form)
+        .await?;
+
+    info!("📤 Upload part добавлен: {} ({} bytes)", part.id(), size);
+    Ok(part.id().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::{
+            Config, ForwardingConfig, ForwardingMode, LogFormat, ModerationAction,
+            ModerationConfig, ServerConfig, UpstreamConfig,
+        },
+        error::Locale,
+    };
+
+    /// Клиент без сети: в проверяемых случаях ни одна часть не отправляется.
+    fn client() -> UpstreamClient {
+        let config = Config {
+            server: ServerConfig {
+                bind: "127.0.0.1:0".parse().unwrap(),
+                body_limit_bytes: 1024,
+                file_body_limit_bytes: 1024,
+                upload_part_body_limit_bytes: 1024,
+                log_format: LogFormat::Text,
+                locale: Locale::En,
+            },
+            upstream: UpstreamConfig::default(),
+            forwarding: ForwardingConfig {
+                mode: ForwardingMode::Typed,
+                headers: Vec::new(),
+            },
+            moderation: ModerationConfig {
+                enabled: false,
+                model: String::new(),
+                action: ModerationAction::Reject,
+                thresholds: Default::default(),
+            },
+        };
+        let state = AppState::new(config).unwrap();
+
+        UpstreamClient::new(&state, "sk-test".to_string(), HeaderMap::new(), false)
+    }
+
+    #[tokio::test]
+    async fn body_longer_than_declared_is_rejected() {
+        let result = upload_parts(&client(), "upload_1", 3, Body::from("hello")).await;
+
+        assert!(matches!(
+            result,
+            Err(AppError::UploadSizeMismatch {
+                declared: 3,
+                received: 5
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn body_shorter_than_declared_is_rejected() {
+        let result = upload_parts(&client(), "upload_1", 5, Body::from("hi")).await;
+
+        assert!(matches!(
+            result,
+            Err(AppError::UploadSizeMismatch {
+                declared: 5,
+                received: 2
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn empty_body_is_rejected_when_bytes_are_declared() {
+        let result = upload_parts(&client(), "upload_1", 1, Body::empty()).await;
+
+        assert!(matches!(
+            result,
+            Err(AppError::UploadSizeMismatch {
+                declared: 1,
+                received: 0
+            })
+        ));
+    }
+}
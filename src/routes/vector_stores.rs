@@ -0,0 +1,549 @@
+//! Обработчики Vector Stores API.
+//!
+//! Хранилища векторов для инструмента `file_search` ассистентов и Responses API:
+//! CRUD хранилищ, файлы и пакеты файлов (file batches), а также поиск по хранилищу.
+
+use crate::{
+    error::AppError,
+    payload::{JsonBody, JsonReply},
+    query::Query,
+    routes::ListQuery,
+    state::AppState,
+    utils::create_client_from_headers,
+};
+use async_openai::types::vectorstores::{
+    CreateVectorStoreFileBatchRequest, CreateVectorStoreFileRequest, CreateVectorStoreRequest,
+    DeleteVectorStoreFileResponse, DeleteVectorStoreResponse, ListVectorStoreFilesResponse,
+    ListVectorStoresResponse, UpdateVectorStoreFileAttributesRequest, UpdateVectorStoreRequest,
+    VectorStoreFileBatchObject, VectorStoreFileContentResponse, VectorStoreFileObject,
+    VectorStoreObject, VectorStoreSearchRequest, VectorStoreSearchResultsPage,
+};
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+};
+use std::sync::Arc;
+use tracing::info;
+
+/// Создает vector store (опционально сразу с файлами из `file_ids`).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `CreateVectorStoreRequest` с именем, файлами и стратегией разбиения
+///
+/// # Returns
+/// * `Ok(JsonReply<VectorStoreObject>)` - Созданный vector store с его `id`
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+///
+/// # Пример
+/// ```bash
+/// curl -X POST http://localhost:8080/v1/vector_stores \
+///   -H "Content-Type: application/json" \
+///   -H "Authorization: Bearer sk-..." \
+///   -d '{"name":"Support FAQ","file_ids":["file-abc123"]}'
+/// ```
+pub async fn create_vector_store(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    request: JsonBody<CreateVectorStoreRequest>,
+) -> Result<JsonReply<VectorStoreObject>, AppError> {
+    info!("🗂️ Create vector store request");
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<VectorStoreObject> = client.post("/vector_stores", &request).await?;
+
+    info!("✅ Vector store создан: {}", response.id());
+    Ok(response)
+}
+
+/// Возвращает список vector stores.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации (`limit`, `order`, `after`, `before`)
+///
+/// # Returns
+/// * `Ok(JsonReply<ListVectorStoresResponse>)` - Страница vector stores (с пагинацией)
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn list_vector_stores(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ListVectorStoresResponse>, AppError> {
+    info!("🗂️ List vector stores request");
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<ListVectorStoresResponse> =
+        client.get_with_query("/vector_stores", &query).await?;
+
+    info!("✅ Vector stores list получен");
+    Ok(response)
+}
+
+/// Возвращает vector store по `vector_store_id` (используется для опроса статуса индексации).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `vector_store_id` - Идентификатор vector store
+/// * `headers` - Authorization заголовок клиента
+///
+/// # Returns
+/// * `Ok(JsonReply<VectorStoreObject>)` - Vector store со статусом и счетчиками файлов
+/// * `Err(AppError)` - Ошибка запроса или vector store не найден
+pub async fn get_vector_store(
+    State(state): State<Arc<AppState>>,
+    Path(vector_store_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<JsonReply<VectorStoreObject>, AppError> {
+    info!("🗂️ Get vector store request: {}", vector_store_id);
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<VectorStoreObject> = client
+        .get(&format!("/vector_stores/{}", vector_store_id))
+        .await?;
+
+    info!("✅ Vector store получен");
+    Ok(response)
+}
+
+/// Изменяет vector store (имя, срок хранения, metadata).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `vector_store_id` - Идентификатор vector store
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `UpdateVectorStoreRequest` с изменяемыми полями
+///
+/// # Returns
+/// * `Ok(JsonReply<VectorStoreObject>)` - Обновленный vector store
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn modify_vector_store(
+    State(state): State<Arc<AppState>>,
+    Path(vector_store_id): Path<String>,
+    headers: HeaderMap,
+    request: JsonBody<UpdateVectorStoreRequest>,
+) -> Result<JsonReply<VectorStoreObject>, AppError> {
+    info!("🗂️ Modify vector store request: {}", vector_store_id);
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<VectorStoreObject> = client
+        .post(&format!("/vector_stores/{}", vector_store_id), &request)
+        .await?;
+
+    info!("✅ Vector store обновлен");
+    Ok(response)
+}
+
+/// Удаляет vector store по `vector_store_id` (сами файлы в Files API не удаляются).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `vector_store_id` - Идентификатор vector store для удаления
+///
+/// # Returns
+/// * `Ok(JsonReply<DeleteVectorStoreResponse>)` - Подтверждение удаления
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn delete_vector_store(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(vector_store_id): Path<String>,
+) -> Result<JsonReply<DeleteVectorStoreResponse>, AppError> {
+    info!("🗂️ Delete vector store request: {}", vector_store_id);
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<DeleteVectorStoreResponse> = client
+        .delete(&format!("/vector_stores/{}", vector_store_id))
+        .await?;
+
+    info!("✅ Vector store удален");
+    Ok(response)
+}
+
+/// Выполняет поиск релевантных фрагментов в vector store.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `vector_store_id` - Идентификатор vector store
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `VectorStoreSearchRequest` с запросом, фильтрами и параметрами ранжирования
+///
+/// # Returns
+/// * `Ok(JsonReply<VectorStoreSearchResultsPage>)` - Найденные фрагменты с оценками релевантности
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+///
+/// # Пример
+/// ```bash
+/// curl -X POST http://localhost:8080/v1/vector_stores/vs_abc123/search \
+///   -H "Content-Type: application/json" \
+///   -H "Authorization: Bearer sk-..." \
+///   -d '{"query":"Как вернуть товар?","max_num_results":5}'
+/// ```
+pub async fn search_vector_store(
+    State(state): State<Arc<AppState>>,
+    Path(vector_store_id): Path<String>,
+    headers: HeaderMap,
+    request: JsonBody<VectorStoreSearchRequest>,
+) -> Result<JsonReply<VectorStoreSearchResultsPage>, AppError> {
+    info!("🔎 Search vector store request: {}", vector_store_id);
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<VectorStoreSearchResultsPage> = client
+        .post(
+            &format!("/vector_stores/{}/search", vector_store_id),
+            &request,
+        )
+        .await?;
+
+    info!("✅ Поиск по vector store выполнен");
+    Ok(response)
+}
+
+/// Добавляет файл из Files API в vector store.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `vector_store_id` - Идентификатор vector store
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `CreateVectorStoreFileRequest` с `file_id`, атрибутами и стратегией разбиения
+///
+/// # Returns
+/// * `Ok(JsonReply<VectorStoreFileObject>)` - Файл vector store (индексация идет асинхронно)
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn create_vector_store_file(
+    State(state): State<Arc<AppState>>,
+    Path(vector_store_id): Path<String>,
+    headers: HeaderMap,
+    request: JsonBody<CreateVectorStoreFileRequest>,
+) -> Result<JsonReply<VectorStoreFileObject>, AppError> {
+    info!("🗂️ Create vector store file request: {}", vector_store_id);
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<VectorStoreFileObject> = client
+        .post(
+            &format!("/vector_stores/{}/files", vector_store_id),
+            &request,
+        )
+        .await?;
+
+    info!("✅ Vector store file создан: {}", response.id());
+    Ok(response)
+}
+
+/// Возвращает список файлов vector store.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `vector_store_id` - Идентификатор vector store
+/// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации и фильтр по статусу `filter`
+///
+/// # Returns
+/// * `Ok(JsonReply<ListVectorStoreFilesResponse>)` - Страница файлов (с пагинацией)
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn list_vector_store_files(
+    State(state): State<Arc<AppState>>,
+    Path(vector_store_id): Path<String>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ListVectorStoreFilesResponse>, AppError> {
+    info!("🗂️ List vector store files request: {}", vector_store_id);
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<ListVectorStoreFilesResponse> = client
+        .get_with_query(&format!("/vector_stores/{}/files", vector_store_id), &query)
+        .await?;
+
+    info!("✅ Vector store files list получен");
+    Ok(response)
+}
+
+/// Возвращает файл vector store (используется для опроса статуса индексации).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `vector_store_id` - Идентификатор vector store
+/// * `file_id` - Идентификатор файла
+/// * `headers` - Authorization заголовок клиента
+///
+/// # Returns
+/// * `Ok(JsonReply<VectorStoreFileObject>)` - Файл vector store со статусом
+/// * `Err(AppError)` - Ошибка запроса или файл не найден
+pub async fn get_vector_store_file(
+    State(state): State<Arc<AppState>>,
+    Path((vector_store_id, file_id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<JsonReply<VectorStoreFileObject>, AppError> {
+    info!(
+        "🗂️ Get vector store file request: {} in vector store: {}",
+        file_id, vector_store_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<VectorStoreFileObject> = client
+        .get(&format!(
+            "/vector_stores/{}/files/{}",
+            vector_store_id, file_id
+        ))
+        .await?;
+
+    info!("✅ Vector store file получен");
+    Ok(response)
+}
+
+/// Обновляет атрибуты файла vector store (используются в фильтрах поиска).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `vector_store_id` - Идентификатор vector store
+/// * `file_id` - Идентификатор файла
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `UpdateVectorStoreFileAttributesRequest` с новыми `attributes`
+///
+/// # Returns
+/// * `Ok(JsonReply<VectorStoreFileObject>)` - Обновленный файл vector store
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn update_vector_store_file(
+    State(state): State<Arc<AppState>>,
+    Path((vector_store_id, file_id)): Path<(String, String)>,
+    headers: HeaderMap,
+    request: JsonBody<UpdateVectorStoreFileAttributesRequest>,
+) -> Result<JsonReply<VectorStoreFileObject>, AppError> {
+    info!(
+        "🗂️ Update vector store file request: {} in vector store: {}",
+        file_id, vector_store_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<VectorStoreFileObject> = client
+        .post(
+            &format!("/vector_stores/{}/files/{}", vector_store_id, file_id),
+            &request,
+        )
+        .await?;
+
+    info!("✅ Vector store file обновлен");
+    Ok(response)
+}
+
+/// Удаляет файл из vector store (сам файл в Files API не удаляется).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - Authorization заголовок клиента
+/// * `vector_store_id` - Идентификатор vector store
+/// * `file_id` - Идентификатор файла для удаления
+///
+/// # Returns
+/// * `Ok(JsonReply<DeleteVectorStoreFileResponse>)` - Подтверждение удаления
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn delete_vector_store_file(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path((vector_store_id, file_id)): Path<(String, String)>,
+) -> Result<JsonReply<DeleteVectorStoreFileResponse>, AppError> {
+    info!(
+        "🗂️ Delete vector store file request: {} in vector store: {}",
+        file_id, vector_store_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<DeleteVectorStoreFileResponse> = client
+        .delete(&format!(
+            "/vector_stores/{}/files/{}",
+            vector_store_id, file_id
+        ))
+        .await?;
+
+    info!("✅ Vector store file удален");
+    Ok(response)
+}
+
+/// Возвращает разобранное текстовое содержимое файла vector store.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `vector_store_id` - Идентификатор vector store
+/// * `file_id` - Идентификатор файла
+/// * `headers` - Authorization заголовок клиента
+///
+/// # Returns
+/// * `Ok(JsonReply<VectorStoreFileContentResponse>)` - Текстовые фрагменты файла
+/// * `Err(AppError)` - Ошибка запроса или файл не найден
+pub async fn get_vector_store_file_content(
+    State(state): State<Arc<AppState>>,
+    Path((vector_store_id, file_id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<JsonReply<VectorStoreFileContentResponse>, AppError> {
+    info!(
+        "🗂️ Get vector store file content request: {} in vector store: {}",
+        file_id, vector_store_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<VectorStoreFileContentResponse> = client
+        .get(&format!(
+            "/vector_stores/{}/files/{}/content",
+            vector_store_id, file_id
+        ))
+        .await?;
+
+    info!("✅ Vector store file content получен");
+    Ok(response)
+}
+
+/// Создает пакет файлов (file batch) для одновременного добавления в vector store.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `vector_store_id` - Идентификатор vector store
+/// * `headers` - Authorization заголовок клиента
+/// * `request` - `CreateVectorStoreFileBatchRequest` со списком `file_ids` или `files`
+///
+/// # Returns
+/// * `Ok(JsonReply<VectorStoreFileBatchObject>)` - Созданный file batch
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn create_vector_store_file_batch(
+    State(state): State<Arc<AppState>>,
+    Path(vector_store_id): Path<String>,
+    headers: HeaderMap,
+    request: JsonBody<CreateVectorStoreFileBatchRequest>,
+) -> Result<JsonReply<VectorStoreFileBatchObject>, AppError> {
+    info!(
+        "🗂️ Create vector store file batch request: {}",
+        vector_store_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<VectorStoreFileBatchObject> = client
+        .post(
+            &format!("/vector_stores/{}/file_batches", vector_store_id),
+            &request,
+        )
+        .await?;
+
+    info!("✅ Vector store file batch создан: {}", response.id());
+    Ok(response)
+}
+
+/// Возвращает file batch (используется для опроса статуса индексации пакета).
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `vector_store_id` - Идентификатор vector store
+/// * `batch_id` - Идентификатор file batch
+/// * `headers` - Authorization заголовок клиента
+///
+/// # Returns
+/// * `Ok(JsonReply<VectorStoreFileBatchObject>)` - File batch со статусом и счетчиками файлов
+/// * `Err(AppError)` - Ошибка запроса или file batch не найден
+pub async fn get_vector_store_file_batch(
+    State(state): State<Arc<AppState>>,
+    Path((vector_store_id, batch_id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<JsonReply<VectorStoreFileBatchObject>, AppError> {
+    info!(
+        "🗂️ Get vector store file batch request: {} in vector store: {}",
+        batch_id, vector_store_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<VectorStoreFileBatchObject> = client
+        .get(&format!(
+            "/vector_stores/{}/file_batches/{}",
+            vector_store_id, batch_id
+        ))
+        .await?;
+
+    info!("✅ Vector store file batch получен");
+    Ok(response)
+}
+
+/// Отменяет обработку file batch.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `vector_store_id` - Идентификатор vector store
+/// * `batch_id` - Идентификатор file batch
+/// * `headers` - Authorization заголовок клиента
+///
+/// # Returns
+/// * `Ok(JsonReply<VectorStoreFileBatchObject>)` - Отмененный file batch
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn cancel_vector_store_file_batch(
+    State(state): State<Arc<AppState>>,
+    Path((vector_store_id, batch_id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<JsonReply<VectorStoreFileBatchObject>, AppError> {
+    info!(
+        "🗂️ Cancel vector store file batch request: {} in vector store: {}",
+        batch_id, vector_store_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<VectorStoreFileBatchObject> = client
+        .post_empty(&format!(
+            "/vector_stores/{}/file_batches/{}/cancel",
+            vector_store_id, batch_id
+        ))
+        .await?;
+
+    info!("✅ Vector store file batch отменен");
+    Ok(response)
+}
+
+/// Возвращает список файлов, входящих в file batch.
+///
+/// # Arguments
+/// * `state` - Состояние приложения с настройками upstream
+/// * `vector_store_id` - Идентификатор vector store
+/// * `batch_id` - Идентификатор file batch
+/// * `headers` - Authorization заголовок клиента
+/// * `query` - Параметры пагинации и фильтр по статусу `filter`
+///
+/// # Returns
+/// * `Ok(JsonReply<ListVectorStoreFilesResponse>)` - Страница файлов пакета (с пагинацией)
+/// * `Err(AppError)` - Ошибка запроса или авторизации
+pub async fn list_vector_store_file_batch_files(
+    State(state): State<Arc<AppState>>,
+    Path((vector_store_id, batch_id)): Path<(String, String)>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<JsonReply<ListVectorStoreFilesResponse>, AppError> {
+    info!(
+        "🗂️ List vector store file batch files request: {} in vector store: {}",
+        batch_id, vector_store_id
+    );
+
+    let client = create_client_from_headers(&state, &headers, true)?;
+
+    let response: JsonReply<ListVectorStoreFilesResponse> = client
+        .get_with_query(
+            &format!(
+                "/vector_stores/{}/file_batches/{}/files",
+                vector_store_id, batch_id
+            ),
+            &query,
+        )
+        .await?;
+
+    info!("✅ Vector store file batch files list получен");
+    Ok(response)
+}
@@ -2,22 +2,35 @@
 //!
 //! Содержит структуру AppState для хранения глобального состояния сервера.
 
+use crate::{config::Config, upstream::build_http_client};
+
 /// Структура состояния приложения.
 ///
-/// Состояние пустое, так как токен OpenAI передается от клиента в каждом запросе
-/// через Authorization заголовок. Это обеспечивает безопасность - токены не хранятся на сервере.
+/// Хранит конфигурацию сервера и общий HTTP клиент с пулом соединений к OpenAI.
+/// Токен OpenAI передается от клиента в каждом запросе через Authorization заголовок
+/// и не хранится на сервере.
 #[derive(Clone)]
 pub struct AppState {
-    // Состояние приложения пустое, так как токен приходит от клиента
+    /// Конфигурация сервера (адрес, upstream, таймауты, лимиты)
+    pub config: Config,
+    /// Общий HTTP клиент к OpenAI API (keep-alive пул, HTTP/2)
+    pub http: reqwest::Client,
 }
 
 impl AppState {
     /// Создает новый экземпляр состояния приложения.
     ///
+    /// # Arguments
+    ///
+    /// * `config` - Загруженная и проверенная конфигурация сервера
+    ///
     /// # Returns
     ///
-    /// Новый экземпляр `AppState`.
-    pub fn new() -> Self {
-        Self {}
+    /// * `Ok(AppState)` - Новый экземпляр состояния
+    /// * `Err(String)` - Не удалось создать HTTP клиент
+    pub fn new(config: Config) -> Result<Self, String> {
+        let http = build_http_client(&config.upstream)?;
+
+        Ok(Self { config, http })
     }
 }
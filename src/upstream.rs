@@ -0,0 +1,727 @@
+//! Модуль HTTP-транспорта к OpenAI API.
+//!
+//! `async-openai` используется как источник типов запросов и ответов, а сами запросы
+//! выполняются здесь: типизированный клиент теряет HTTP статус и тело ошибок OpenAI
+//! и не позволяет ретранслировать SSE-потоки без повторной сериализации событий.
+
+use crate::{
+    config::{ForwardingMode, UpstreamConfig},
+    error::AppError,
+    payload::JsonReply,
+    state::AppState,
+};
+use axum::{
+    body::{Body, Bytes},
+    http::{
+        header::{
+            ACCEPT, AUTHORIZATION, CACHE_CONTROL, CONNECTION, CONTENT_DISPOSITION, CONTENT_LENGTH,
+            CONTENT_TYPE, HOST,
+        },
+        HeaderMap, HeaderName, HeaderValue, StatusCode,
+    },
+    response::Response,
+};
+use reqwest::{multipart::Form, Method, RequestBuilder};
+use serde::{de::DeserializeOwned, Serialize};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    tungstenite::{self, client::IntoClientRequest, http::header::SEC_WEBSOCKET_PROTOCOL},
+    MaybeTlsStream, WebSocketStream,
+};
+use tracing::Span;
+
+/// Заголовок beta-флагов OpenAI API.
+const OPENAI_BETA: &str = "openai-beta";
+/// Beta-флаг Assistants API v2.
+const ASSISTANTS_V2: &str = "assistants=v2";
+/// Ключ идемпотентности запроса клиента.
+const IDEMPOTENCY_KEY: &str = "idempotency-key";
+
+/// Заголовки ответа OpenAI, которые передаются клиенту из типизированных обработчиков:
+/// идентификатор запроса (нужен поддержке OpenAI), время обработки и версия API.
+const RELAYED_HEADERS: [&str; 3] = ["x-request-id", "openai-processing-ms", "openai-version"];
+/// Префикс заголовков лимитов OpenAI (`x-ratelimit-remaining-requests`, `x-ratelimit-reset-tokens`...).
+const RATE_LIMIT_HEADERS_PREFIX: &str = "x-ratelimit-";
+/// Заголовки содержимого, которые ретранслируемые ответы (файлы, аудио, SSE) сохраняют.
+const CONTENT_HEADERS: [HeaderName; 3] = [CONTENT_TYPE, CONTENT_LENGTH, CONTENT_DISPOSITION];
+
+/// Hop-by-hop заголовки (RFC 9110), которые относятся к конкретному соединению
+/// и не пересылаются при сквозном проксировании.
+const HOP_BY_HOP_HEADERS: [&str; 9] = [
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "proxy-connection",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/// WebSocket соединение с OpenAI API (`ws://` или `wss://` в зависимости от `base_url`).
+pub type UpstreamWebSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Создает общий HTTP клиент для запросов к OpenAI API.
+///
+/// Клиент хранит пул keep-alive соединений (HTTP/2, если upstream его поддерживает)
+/// и создается один раз при старте сервера. API ключ в клиенте не хранится и
+/// подставляется в каждый запрос отдельно.
+///
+/// # Arguments
+///
+/// * `config` - Настройки upstream (таймауты и параметры пула соединений)
+///
+/// # Returns
+///
+/// * `Ok(reqwest::Client)` - HTTP клиент с пулом соединений
+/// * `Err(String)` - Не удалось инициализировать HTTP клиент (например, TLS)
+pub fn build_http_client(config: &UpstreamConfig) -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .connect_timeout(config.connect_timeout)
+        .read_timeout(config.read_timeout)
+        .pool_max_idle_per_host(config.pool_max_idle_per_host)
+        .pool_idle_timeout(config.pool_idle_timeout)
+        .build()
+        .map_err(|e| format!("не удалось создать HTTP клиент: {}", e))
+}
+
+/// Клиент OpenAI API, привязанный к API ключу конкретного запроса.
+pub struct UpstreamClient {
+    http: reqwest::Client,
+    base_url: String,
+    api_key: String,
+    headers: HeaderMap,
+    mode: ForwardingMode,
+    connect_timeout: Duration,
+}
+
+impl UpstreamClient {
+    /// Создает клиента для API ключа клиента.
+    ///
+    /// Использует общий HTTP клиент из состояния приложения, поэтому соединения
+    /// с OpenAI (и TLS сессии) переиспользуются между запросами разных клиентов прокси.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - Состояние приложения (общий HTTP клиент и конфигурация)
+    /// * `api_key` - API ключ OpenAI из Authorization заголовка клиента
+    /// * `headers` - Заголовки клиента из allowlist, добавляемые в каждый запрос
+    /// * `use_beta` - Если `true`, добавляет `assistants=v2` в заголовок `OpenAI-Beta` для Assistants API v2
+    ///
+    /// # Returns
+    ///
+    /// Новый экземпляр `UpstreamClient`.
+    pub fn new(state: &AppState, api_key: String, mut headers: HeaderMap, use_beta: bool) -> Self {
+        if use_beta {
+            // Сохраняем beta-флаги клиента и добавляем к ним assistants=v2, если его нет
+            let beta = match headers.get(OPENAI_BETA).and_then(|v| v.to_str().ok()) {
+                Some(client_beta) if client_beta.contains("assistants=") => None,
+                Some(client_beta) => {
+                    HeaderValue::from_str(&format!("{}, {}", client_beta, ASSISTANTS_V2)).ok()
+                }
+                None => Some(HeaderValue::from_static(ASSISTANTS_V2)),
+            };
+            if let Some(beta) = beta {
+                headers.insert(OPENAI_BETA, beta);
+            }
+        }
+
+        Self {
+            http: state.http.clone(),
+            base_url: state.config.upstream.base_url.clone(),
+            api_key,
+            headers,
+            mode: state.config.forwarding.mode,
+            connect_timeout: state.config.upstream.connect_timeout,
+        }
+    }
+
+    /// Возвращает копию клиента без заголовка `Idempotency-Key` клиента.
+    ///
+    /// Используется для служебных запросов, которые прокси выполняет сам в рамках одного
+    /// запроса клиента: с общим ключом OpenAI вернул бы на каждый из них первый ответ
+    /// или отклонил бы их как конфликтующие.
+    pub fn without_idempotency_key(&self) -> Self {
+        let mut headers = self.headers.clone();
+        headers.remove(IDEMPOTENCY_KEY);
+
+        Self {
+            http: self.http.clone(),
+            base_url: self.base_url.clone(),
+            api_key: self.api_key.clone(),
+            headers,
+            mode: self.mode,
+            connect_timeout: self.connect_timeout,
+        }
+    }
+
+    /// Выполняет GET запрос и десериализует JSON ответ.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Путь эндпоинта относительно `/v1` (например, `/models`)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonReply<O>)` - JSON ответ OpenAI
+    /// * `Err(AppError)` - Ошибка соединения или ошибка, возвращенная OpenAI
+    pub async fn get<O: DeserializeOwned + Serialize>(
+        &self,
+        path: &str,
+    ) -> Result<JsonReply<O>, AppError> {
+        self.execute(self.request(Method::GET, path)).await
+    }
+
+    /// Выполняет GET запрос с параметрами query string (пагинация, фильтры)
+    /// и разбирает JSON ответ.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Путь эндпоинта относительно `/v1`
+    /// * `query` - Параметры query string, сериализуемые в `application/x-www-form-urlencoded`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonReply<O>)` - JSON ответ OpenAI
+    /// * `Err(AppError)` - Ошибка соединения или ошибка, возвращенная OpenAI
+    pub async fn get_with_query<Q: Serialize + ?Sized, O: DeserializeOwned + Serialize>(
+        &self,
+        path: &str,
+        query: &Q,
+    ) -> Result<JsonReply<O>, AppError> {
+        self.execute(self.request(Method::GET, path).query(query))
+            .await
+    }
+
+    /// Выполняет POST запрос с JSON телом и десериализует JSON ответ.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Путь эндпоинта относительно `/v1`
+    /// * `body` - Тело запроса, сериализуемое в JSON
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonReply<O>)` - JSON ответ OpenAI
+    /// * `Err(AppError)` - Ошибка соединения или ошибка, возвращенная OpenAI
+    pub async fn post<I: Serialize + ?Sized, O: DeserializeOwned + Serialize>(
+        &self,
+        path: &str,
+        body: &I,
+    ) -> Result<JsonReply<O>, AppError> {
+        self.execute(self.request(Method::POST, path).json(body))
+            .await
+    }
+
+    /// Выполняет POST запрос с параметрами query string (например, `include[]`)
+    /// и JSON телом и десериализует JSON ответ.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Путь эндпоинта относительно `/v1`
+    /// * `query` - Параметры query string, сериализуемые в `application/x-www-form-urlencoded`
+    /// * `body` - Тело запроса, сериализуемое в JSON
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonReply<O>)` - JSON ответ OpenAI
+    /// * `Err(AppError)` - Ошибка соединения или ошибка, возвращенная OpenAI
+    pub async fn post_with_query<
+        Q: Serialize + ?Sized,
+        I: Serialize + ?Sized,
+        O: DeserializeOwned + Serialize,
+    >(
+        &self,
+        path: &str,
+        query: &Q,
+        body: &I,
+    ) -> Result<JsonReply<O>, AppError> {
+        self.execute(self.request(Method::POST, path).query(query).json(body))
+            .await
+    }
+
+    /// Выполняет POST запрос без тела (например, отмена run или response).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Путь эндпоинта относительно `/v1`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonReply<O>)` - JSON ответ OpenAI
+    /// * `Err(AppError)` - Ошибка соединения или ошибка, возвращенная OpenAI
+    pub async fn post_empty<O: DeserializeOwned + Serialize>(
+        &self,
+        path: &str,
+    ) -> Result<JsonReply<O>, AppError> {
+        self.execute(self.request(Method::POST, path)).await
+    }
+
+    /// Выполняет DELETE запрос и десериализует JSON ответ.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Путь эндпоинта относительно `/v1`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonReply<O>)` - JSON ответ OpenAI
+    /// * `Err(AppError)` - Ошибка соединения или ошибка, возвращенная OpenAI
+    pub async fn delete<O: DeserializeOwned + Serialize>(
+        &self,
+        path: &str,
+    ) -> Result<JsonReply<O>, AppError> {
+        self.execute(self.request(Method::DELETE, path)).await
+    }
+
+    /// Выполняет POST запрос с multipart/form-data телом и десериализует JSON ответ.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Путь эндпоинта относительно `/v1`
+    /// * `form` - Поля multipart формы
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonReply<O>)` - JSON ответ OpenAI
+    /// * `Err(AppError)` - Ошибка соединения или ошибка, возвращенная OpenAI
+    pub async fn post_multipart<O: DeserializeOwned + Serialize>(
+        &self,
+        path: &str,
+        form: Form,
+    ) -> Result<JsonReply<O>, AppError> {
+        self.execute(self.request(Method::POST, path).multipart(form))
+            .await
+    }
+
+    /// Выполняет GET запрос и ретранслирует ответ OpenAI как есть.
+    ///
+    /// Используется для содержимого файлов: тело передается клиенту потоком, без
+    /// буферизации в памяти прокси, с исходными `Content-Type`, `Content-Length`
+    /// и `Content-Disposition`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Путь эндпоинта относительно `/v1`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Response)` - Ответ OpenAI с исходными заголовками содержимого и телом
+    /// * `Err(AppError)` - Ошибка соединения или ошибка, возвращенная OpenAI
+    pub async fn get_relay(&self, path: &str) -> Result<Response, AppError> {
+        self.relay(self.request(Method::GET, path)).await
+    }
+
+    /// Отправляет POST запрос с JSON телом и ретранслирует ответ OpenAI как есть.
+    ///
+    /// Используется для ответов, которые не являются JSON (например, аудио из
+    /// `/audio/speech`): тело передается клиенту потоком с исходным Content-Type.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Путь эндпоинта относительно `/v1`
+    /// * `body` - Тело запроса, сериализуемое в JSON
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Response)` - Ответ OpenAI с исходными Content-Type и телом
+    /// * `Err(AppError)` - Ошибка соединения или ошибка, возвращенная OpenAI
+    pub async fn post_relay<I: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        body: &I,
+    ) -> Result<Response, AppError> {
+        self.relay(self.request(Method::POST, path).json(body))
+            .await
+    }
+
+    /// Отправляет POST запрос с multipart/form-data телом и ретранслирует ответ OpenAI как есть.
+    ///
+    /// Подходит для эндпоинтов, формат ответа которых выбирает клиент (`json`, `text`,
+    /// `srt`, `vtt`, SSE при `stream=true`).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Путь эндпоинта относительно `/v1`
+    /// * `form` - Поля multipart формы
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Response)` - Ответ OpenAI с исходными Content-Type и телом
+    /// * `Err(AppError)` - Ошибка соединения или ошибка, возвращенная OpenAI
+    pub async fn post_multipart_relay(&self, path: &str, form: Form) -> Result<Response, AppError> {
+        self.relay(self.request(Method::POST, path).multipart(form))
+            .await
+    }
+
+    /// Отправляет POST запрос с `stream: true` и ретранслирует SSE-поток клиенту.
+    ///
+    /// Тело ответа OpenAI передается клиенту по мере поступления чанков, без буферизации
+    /// и без изменения содержимого (включая финальное событие `data: [DONE]`).
+    /// Если клиент разрывает соединение, axum освобождает тело ответа, вместе с ним
+    /// закрывается и соединение с OpenAI, поэтому генерация на стороне API прекращается.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Путь эндпоинта относительно `/v1` (например, `/chat/completions`)
+    /// * `body` - Тело запроса, сериализуемое в JSON
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Response)` - Ответ `text/event-stream`, тело которого читается из OpenAI
+    /// * `Err(AppError)` - Ошибка соединения или ошибка, возвращенная OpenAI
+    pub async fn post_stream<I: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        body: &I,
+    ) -> Result<Response, AppError> {
+        let request = self
+            .request(Method::POST, path)
+            .header(ACCEPT, "text/event-stream")
+            .json(body);
+
+        self.relay_sse(request).await
+    }
+
+    /// Отправляет POST запрос с параметрами query string и `stream: true`
+    /// и ретранслирует SSE-поток клиенту (см. [`UpstreamClient::post_stream`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Путь эндпоинта относительно `/v1`
+    /// * `query` - Параметры query string, сериализуемые в `application/x-www-form-urlencoded`
+    /// * `body` - Тело запроса, сериализуемое в JSON
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Response)` - Ответ `text/event-stream`, тело которого читается из OpenAI
+    /// * `Err(AppError)` - Ошибка соединения или ошибка, возвращенная OpenAI
+    pub async fn post_stream_with_query<Q: Serialize + ?Sized, I: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        query: &Q,
+        body: &I,
+    ) -> Result<Response, AppError> {
+        let request = self
+            .request(Method::POST, path)
+            .header(ACCEPT, "text/event-stream")
+            .query(query)
+            .json(body);
+
+        self.relay_sse(request).await
+    }
+
+    /// Отправляет GET запрос и ретранслирует SSE-поток клиенту.
+    ///
+    /// Используется для возобновления потоков (например, фоновых responses), когда
+    /// параметры потока передаются в query string.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Путь эндпоинта относительно `/v1`
+    /// * `query` - Параметры query string, сериализуемые в `application/x-www-form-urlencoded`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Response)` - Ответ `text/event-stream`, тело которого читается из OpenAI
+    /// * `Err(AppError)` - Ошибка соединения или ошибка, возвращенная OpenAI
+    pub async fn get_stream<Q: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        query: &Q,
+    ) -> Result<Response, AppError> {
+        let request = self
+            .request(Method::GET, path)
+            .header(ACCEPT, "text/event-stream")
+            .query(query);
+
+        self.relay_sse(request).await
+    }
+
+    /// Пересылает запрос в OpenAI API без разбора тела и ретранслирует ответ как есть.
+    ///
+    /// Заголовки клиента передаются без изменений, кроме hop-by-hop заголовков, `Host`
+    /// (выставляется заново) и `Authorization` (подставляется ключ клиента). Тело клиента
+    /// передается в OpenAI потоком, без буферизации в памяти прокси, поэтому лимит
+    /// `server.body_limit_bytes` к нему не применяется. Ответ OpenAI, включая ошибки,
+    /// возвращается с исходными статусом, заголовками и телом, которое читается потоком.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - HTTP метод запроса клиента
+    /// * `path_and_query` - Путь относительно `/v1` вместе с query string
+    /// * `headers` - Заголовки запроса клиента
+    /// * `body` - Тело запроса клиента
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Response)` - Ответ OpenAI API
+    /// * `Err(AppError)` - Ошибка соединения с OpenAI
+    pub async fn forward(
+        &self,
+        method: Method,
+        path_and_query: &str,
+        headers: &HeaderMap,
+        body: Body,
+    ) -> Result<Response, AppError> {
+        let mut request = self.request(method, path_and_query);
+        let connection = connection_headers(headers);
+        for (name, value) in headers {
+            // Заголовки из allowlist уже добавлены в request()
+            if is_hop_by_hop(name, &connection)
+                || self.headers.contains_key(name)
+                || [AUTHORIZATION, HOST].contains(name)
+            {
+                continue;
+            }
+            request = request.header(name, value);
+        }
+
+        // Content-Length клиента сохраняется, без него тело передается chunked
+        let body = reqwest::Body::wrap_stream(body.into_data_stream());
+        let upstream = request.body(body).send().await.map_err(map_reqwest_error)?;
+        record_upstream_request_id(upstream.headers());
+
+        let mut response = Response::builder().status(upstream.status());
+        let connection = connection_headers(upstream.headers());
+        for (name, value) in upstream.headers() {
+            if !is_hop_by_hop(name, &connection) {
+                response = response.header(name, value);
+            }
+        }
+
+        response
+            .body(Body::from_stream(upstream.bytes_stream()))
+            .map_err(|e| AppError::Internal(format!("Passthrough response error: {}", e)))
+    }
+
+    /// Открывает WebSocket соединение с OpenAI API (например, Realtime API).
+    ///
+    /// Схема `base_url` заменяется на `ws`/`wss`, в handshake передаются ключ клиента,
+    /// заголовки из allowlist и `Sec-WebSocket-Protocol` клиента (если он есть).
+    /// Если OpenAI отклонил handshake HTTP ответом (например, 401 для неверного ключа),
+    /// ответ возвращается как `AppError::Upstream` с исходными статусом и телом.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Путь эндпоинта относительно `/v1`
+    /// * `query` - Параметры query string клиента
+    /// * `protocols` - Заголовок `Sec-WebSocket-Protocol` клиента
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((UpstreamWebSocket, HeaderMap))` - Соединение и заголовки handshake OpenAI для
+    ///   клиента (`x-request-id`, `x-ratelimit-*` и выбранный `Sec-WebSocket-Protocol`)
+    /// * `Err(AppError)` - Ошибка соединения, таймаут или отказ OpenAI в handshake
+    pub async fn connect_websocket(
+        &self,
+        path: &str,
+        query: &[(String, String)],
+        protocols: Option<&HeaderValue>,
+    ) -> Result<(UpstreamWebSocket, HeaderMap), AppError> {
+        let mut url = reqwest::Url::parse(&format!("{}{}", self.base_url, path))
+            .map_err(|e| AppError::Internal(format!("WebSocket URL error: {}", e)))?;
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+        let url = match url.as_str().split_once("://") {
+            Some(("https", rest)) => format!("wss://{}", rest),
+            Some(("http", rest)) => format!("ws://{}", rest),
+            _ => url.to_string(),
+        };
+
+        let mut request = url
+            .into_client_request()
+            .map_err(|e| AppError::Internal(format!("WebSocket request error: {}", e)))?;
+        let headers = request.headers_mut();
+        headers.extend(self.headers.clone());
+        let authorization = HeaderValue::from_str(&format!("Bearer {}", self.api_key))
+            .map_err(|_| AppError::AuthMalformed)?;
+        headers.insert(AUTHORIZATION, authorization);
+        if let Some(protocols) = protocols {
+            headers.insert(SEC_WEBSOCKET_PROTOCOL, protocols.clone());
+        }
+
+        let connect = tokio_tungstenite::connect_async(request);
+        let (socket, response) = tokio::time::timeout(self.connect_timeout, connect)
+            .await
+            .map_err(|_| AppError::UpstreamTimeout("WebSocket handshake timed out".to_string()))?
+            .map_err(map_websocket_error)?;
+
+        record_upstream_request_id(response.headers());
+
+        let mut headers = relayed_headers(response.headers());
+        if let Some(protocol) = response.headers().get(SEC_WEBSOCKET_PROTOCOL) {
+            headers.insert(SEC_WEBSOCKET_PROTOCOL, protocol.clone());
+        }
+
+        Ok((socket, headers))
+    }
+
+    /// Создает запрос к OpenAI API с авторизацией клиента.
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http
+            .request(method, format!("{}{}", self.base_url, path))
+            .bearer_auth(&self.api_key)
+            .headers(self.headers.clone())
+    }
+
+    /// Выполняет запрос и разбирает JSON ответ в соответствии с режимом пересылки.
+    async fn execute<O: DeserializeOwned + Serialize>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<JsonReply<O>, AppError> {
+        let response = self.send(request).await?;
+        let headers = relayed_headers(response.headers());
+        let bytes = response.bytes().await.map_err(map_reqwest_error)?;
+
+        JsonReply::parse(&bytes, self.mode, headers)
+    }
+
+    /// Отправляет запрос и проверяет статус ответа.
+    ///
+    /// `x-request-id` OpenAI записывается в span текущего запроса для логов.
+    /// Неуспешный ответ OpenAI превращается в `AppError::Upstream` с исходными
+    /// статусом, телом и заголовками лимитов.
+    async fn send(&self, request: RequestBuilder) -> Result<reqwest::Response, AppError> {
+        let response = request.send().await.map_err(map_reqwest_error)?;
+
+        record_upstream_request_id(response.headers());
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let mut headers = relayed_headers(response.headers());
+        if let Some(content_type) = response.headers().get(CONTENT_TYPE) {
+            headers.insert(CONTENT_TYPE, content_type.clone());
+        }
+        let body = response.bytes().await.map_err(map_reqwest_error)?;
+
+        Err(AppError::Upstream {
+            status,
+            headers: Box::new(headers),
+            body,
+        })
+    }
+
+    /// Выполняет запрос и передает клиенту тело ответа потоком с исходными заголовками
+    /// содержимого (`Content-Type`, `Content-Length`, `Content-Disposition`).
+    ///
+    /// SSE-ответы дополнительно получают заголовки, отключающие кеширование и буферизацию.
+    async fn relay(&self, request: RequestBuilder) -> Result<Response, AppError> {
+        let upstream = self.send(request).await?;
+
+        let mut headers = relayed_headers(upstream.headers());
+        if let Some(content_type) = upstream.headers().get(CONTENT_TYPE) {
+            if content_type.as_bytes().starts_with(b"text/event-stream") {
+                headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+                headers.insert("X-Accel-Buffering", HeaderValue::from_static("no"));
+            }
+        }
+        for name in CONTENT_HEADERS {
+            if let Some(value) = upstream.headers().get(&name) {
+                headers.insert(name, value.clone());
+            }
+        }
+
+        let mut response = Response::builder().status(upstream.status());
+        if let Some(response_headers) = response.headers_mut() {
+            response_headers.extend(headers);
+        }
+
+        response
+            .body(Body::from_stream(upstream.bytes_stream()))
+            .map_err(|e| AppError::Internal(format!("Relay response error: {}", e)))
+    }
+
+    /// Выполняет запрос и оборачивает тело ответа в SSE-ответ axum.
+    async fn relay_sse(&self, request: RequestBuilder) -> Result<Response, AppError> {
+        let upstream = self.send(request).await?;
+
+        let mut response = Response::builder().status(StatusCode::OK);
+        if let Some(headers) = response.headers_mut() {
+            headers.extend(relayed_headers(upstream.headers()));
+        }
+
+        response
+            .header(CONTENT_TYPE, "text/event-stream")
+            .header(CACHE_CONTROL, "no-cache")
+            // Отключаем буферизацию в nginx и подобных reverse proxy
+            .header("X-Accel-Buffering", "no")
+            .body(Body::from_stream(upstream.bytes_stream()))
+            .map_err(|e| AppError::Internal(format!("Stream response error: {}", e)))
+    }
+}
+
+/// Выбирает из ответа OpenAI заголовки, которые передаются клиенту
+/// (`x-request-id`, `openai-processing-ms`, `openai-version`, `x-ratelimit-*`).
+fn relayed_headers(upstream: &HeaderMap) -> HeaderMap {
+    upstream
+        .iter()
+        .filter(|(name, _)| {
+            RELAYED_HEADERS.contains(&name.as_str())
+                || name.as_str().starts_with(RATE_LIMIT_HEADERS_PREFIX)
+        })
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect()
+}
+
+/// Записывает `x-request-id` ответа OpenAI в span текущего запроса.
+fn record_upstream_request_id(headers: &HeaderMap) {
+    if let Some(id) = headers.get("x-request-id").and_then(|v| v.to_str().ok()) {
+        Span::current().record("upstream_request_id", id);
+    }
+}
+
+/// Возвращает заголовки, перечисленные в `Connection` (RFC 9110 §7.6.1).
+fn connection_headers(headers: &HeaderMap) -> Vec<HeaderName> {
+    headers
+        .get_all(CONNECTION)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|name| HeaderName::from_bytes(name.trim().as_bytes()).ok())
+        .collect()
+}
+
+/// Проверяет, является ли заголовок hop-by-hop: из стандартного списка
+/// или перечисленный в заголовке `Connection` того же сообщения.
+fn is_hop_by_hop(name: &HeaderName, connection: &[HeaderName]) -> bool {
+    HOP_BY_HOP_HEADERS.contains(&name.as_str()) || connection.contains(name)
+}
+
+/// Преобразует ошибку WebSocket handshake с OpenAI в ошибку прокси.
+///
+/// HTTP ответ, которым OpenAI отклонил handshake, передается клиенту без изменений.
+fn map_websocket_error(e: tungstenite::Error) -> AppError {
+    match e {
+        tungstenite::Error::Http(response) => {
+            let mut headers = relayed_headers(response.headers());
+            if let Some(content_type) = response.headers().get(CONTENT_TYPE) {
+                headers.insert(CONTENT_TYPE, content_type.clone());
+            }
+            let status = response.status();
+            let body = response.into_body().map(Bytes::from).unwrap_or_default();
+
+            AppError::Upstream {
+                status,
+                headers: Box::new(headers),
+                body,
+            }
+        }
+        e => AppError::UpstreamUnreachable(e.to_string()),
+    }
+}
+
+/// Преобразует ошибку соединения с OpenAI в ошибку прокси.
+fn map_reqwest_error(e: reqwest::Error) -> AppError {
+    if e.is_timeout() {
+        AppError::UpstreamTimeout(e.to_string())
+    } else {
+        AppError::UpstreamUnreachable(e.to_string())
+    }
+}
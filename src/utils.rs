@@ -2,43 +2,30 @@
 //!
 //! Содержит вспомогательные функции для работы с HTTP запросами и OpenAI клиентами.
 
-use crate::error::AppError;
-use async_openai::{config::OpenAIConfig, Client as OpenAIClient};
+use crate::{error::AppError, state::AppState, upstream::UpstreamClient};
 use axum::http::HeaderMap;
 
-/// Извлекает токен из Authorization заголовка и создает OpenAI клиента.
+/// Извлекает API ключ OpenAI из Authorization заголовка.
 ///
-/// Функция ищет заголовок Authorization (регистронезависимо), извлекает из него
-/// API ключ OpenAI (с удалением префикса "Bearer " если присутствует) и создает
-/// конфигурированного клиента для взаимодействия с OpenAI API.
+/// Заголовок ищется регистронезависимо, префикс "Bearer " (если есть) удаляется.
 ///
 /// # Arguments
 ///
 /// * `headers` - HTTP заголовки запроса
-/// * `use_beta` - Если `true`, добавляет заголовок `OpenAI-Beta: assistants=v2` для Assistants API v2
 ///
 /// # Returns
 ///
-/// * `Ok(OpenAIClient)` - Сконфигурированный OpenAI клиент
-/// * `Err(AppError)` - Если заголовок отсутствует, имеет неверный формат или ключ пустой
-///
-/// # Examples
-///
-/// ```rust,ignore
-/// let client = create_client_from_headers(&headers, false)?;
-/// let response = client.chat().create(request).await?;
-/// ```
-pub fn create_client_from_headers(headers: &HeaderMap, use_beta: bool) -> Result<OpenAIClient<OpenAIConfig>, AppError> {
+/// * `Ok(String)` - API ключ клиента
+/// * `Err(AppError)` - 401, если заголовок отсутствует, имеет неверный формат или ключ пустой
+pub fn extract_api_key(headers: &HeaderMap) -> Result<String, AppError> {
     // Получаем Authorization заголовок
     let auth_header = headers
         .get("authorization")
         .or_else(|| headers.get("Authorization"))
-        .ok_or_else(|| AppError("Authorization заголовок не найден".to_string()))?;
+        .ok_or(AppError::AuthMissing)?;
 
     // Извлекаем токен
-    let auth_str = auth_header
-        .to_str()
-        .map_err(|_| AppError("Неверный формат Authorization заголовка".to_string()))?;
+    let auth_str = auth_header.to_str().map_err(|_| AppError::AuthMalformed)?;
 
     // Убираем "Bearer " если есть
     let api_key = auth_str
@@ -48,18 +35,49 @@ pub fn create_client_from_headers(headers: &HeaderMap, use_beta: bool) -> Result
         .to_string();
 
     if api_key.is_empty() {
-        return Err(AppError("API ключ пустой".to_string()));
+        return Err(AppError::AuthEmpty);
     }
 
-    // Создаем OpenAI клиента с полученным токеном
-    let mut config = OpenAIConfig::new().with_api_key(api_key);
-    
-    // Если нужен Beta API, добавляем соответствующий заголовок
-    if use_beta {
-        config = config
-            .with_header("OpenAI-Beta", "assistants=v2")
-            .map_err(|e| AppError(format!("Ошибка создания конфигурации: {}", e)))?;
+    Ok(api_key)
+}
+
+/// Извлекает токен из Authorization заголовка и создает OpenAI клиента.
+///
+/// Функция получает API ключ через [`extract_api_key`] и создает
+/// клиента для взаимодействия с OpenAI API от имени клиента прокси.
+/// Заголовки клиента из allowlist `forwarding.headers` (по умолчанию `OpenAI-Organization`,
+/// `OpenAI-Project`, `OpenAI-Beta`, `Idempotency-Key`) передаются в OpenAI без изменений.
+///
+/// # Arguments
+///
+/// * `state` - Состояние приложения с настройками upstream
+/// * `headers` - HTTP заголовки запроса
+/// * `use_beta` - Если `true`, добавляет заголовок `OpenAI-Beta: assistants=v2` для Assistants API v2
+///
+/// # Returns
+///
+/// * `Ok(UpstreamClient)` - Сконфигурированный OpenAI клиент
+/// * `Err(AppError)` - Если заголовок отсутствует, имеет неверный формат или ключ пустой
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let client = create_client_from_headers(&state, &headers, false)?;
+/// let response: JsonReply<CreateChatCompletionResponse> = client.post("/chat/completions", &request).await?;
+/// ```
+pub fn create_client_from_headers(
+    state: &AppState,
+    headers: &HeaderMap,
+    use_beta: bool,
+) -> Result<UpstreamClient, AppError> {
+    let api_key = extract_api_key(headers)?;
+
+    let mut forwarded = HeaderMap::new();
+    for name in &state.config.forwarding.headers {
+        for value in headers.get_all(name) {
+            forwarded.append(name.clone(), value.clone());
+        }
     }
-    
-    Ok(OpenAIClient::with_config(config))
+
+    Ok(UpstreamClient::new(state, api_key, forwarded, use_beta))
 }
@@ -0,0 +1,26 @@
+//! Модуль WebSocket upgrade.
+//!
+//! Экстрактор [`WebSocketUpgrade`] заменяет `axum::extract::ws::WebSocketUpgrade`: запросы
+//! без корректного upgrade (например, обычный `GET /v1/realtime`) получают ответ в схеме
+//! ошибок прокси (`proxy_websocket_upgrade_invalid`), а не текстом axum.
+
+use crate::error::AppError;
+use axum::{extract::FromRequestParts, http::request::Parts};
+
+/// WebSocket upgrade запроса клиента.
+pub struct WebSocketUpgrade(pub axum::extract::ws::WebSocketUpgrade);
+
+impl<S> FromRequestParts<S> for WebSocketUpgrade
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let upgrade = axum::extract::ws::WebSocketUpgrade::from_request_parts(parts, state)
+            .await
+            .map_err(AppError::from_websocket_upgrade_rejection)?;
+
+        Ok(Self(upgrade))
+    }
+}